@@ -0,0 +1,155 @@
+use perlin2d::PerlinNoise2D;
+use rand::{rngs::StdRng, Rng};
+use crate::{
+    blocks::{BlockKind, Block, tree::Tree, stones::Stones, sticks::Sticks, rock::Rock, grasstuft::GrassTuft, bush::Bush},
+    chunk::Terrain,
+    river::RiverCache,
+};
+
+/// Noise maps the world is generated from.
+pub struct WorldNoise {
+    pub height: PerlinNoise2D,
+    pub temperature: PerlinNoise2D,
+    pub moisture: PerlinNoise2D,
+    pub rivers: RiverCache,
+}
+
+impl WorldNoise {
+    pub fn new(seed: u32) -> Self {
+        let height = PerlinNoise2D::new(
+            1,
+            100.0,
+            5.0, // 5.0
+            1.0,
+            2.0,
+            (100.0, 100.0),
+            0.5,
+            seed as i32
+        );
+        // climate changes slowly so that biomes spread over many chunks
+        let temperature = PerlinNoise2D::new(2, 100.0, 5.0, 1.0, 2.0, (400.0, 400.0), 0.5, seed.wrapping_add(1) as i32);
+        let moisture = PerlinNoise2D::new(2, 100.0, 5.0, 1.0, 2.0, (400.0, 400.0), 0.5, seed.wrapping_add(2) as i32);
+        Self {
+            height,
+            temperature,
+            moisture,
+            rivers: RiverCache::default(),
+        }
+    }
+
+    pub fn biome(&self, x: f64, y: f64) -> Biome {
+        Biome::pick(
+            self.height.get_noise(x, y),
+            self.temperature.get_noise(x, y),
+            self.moisture.get_noise(x, y),
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Biome {
+    Plains,
+    Forest,
+    Desert,
+    Tundra,
+    Swamp,
+    Mountains,
+    Beach,
+}
+
+impl Biome {
+    pub fn pick(height: f64, temperature: f64, moisture: f64) -> Self {
+        if height >= 30.0 {
+            return Biome::Mountains;
+        }
+        let climate = if temperature < -28.0 {
+            Biome::Tundra
+        } else if temperature > 15.0 && moisture < 0.0 {
+            Biome::Desert
+        } else if moisture > 28.0 {
+            Biome::Swamp
+        } else if moisture > 5.0 {
+            Biome::Forest
+        } else {
+            Biome::Plains
+        };
+        match climate {
+            Biome::Plains | Biome::Forest | Biome::Desert if (0.0..4.0).contains(&height) => Biome::Beach,
+            _ => climate,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Biome::Plains => "plains",
+            Biome::Forest => "forest",
+            Biome::Desert => "desert",
+            Biome::Tundra => "tundra",
+            Biome::Swamp => "swamp",
+            Biome::Mountains => "mountains",
+            Biome::Beach => "beach",
+        }
+    }
+
+    /// Chances in per mille of (tree, stones, sticks, grass tuft, bush) on open ground.
+    fn densities(&self) -> (u32, u32, u32, u32, u32) {
+        match self {
+            Biome::Plains => (67, 10, 10, 0, 8),
+            Biome::Forest => (250, 5, 25, 10, 15),
+            Biome::Desert => (0, 15, 3, 0, 0),
+            Biome::Tundra => (30, 12, 5, 0, 3),
+            Biome::Swamp => (80, 0, 15, 120, 5),
+            Biome::Mountains => (0, 0, 0, 0, 0),
+            Biome::Beach => (0, 12, 15, 0, 0),
+        }
+    }
+
+    fn ground(&self) -> Terrain {
+        match self {
+            Biome::Plains => Terrain::Grass,
+            Biome::Forest => Terrain::Moss,
+            Biome::Desert => Terrain::Sand,
+            Biome::Tundra => Terrain::Snow,
+            Biome::Swamp => Terrain::Mud,
+            Biome::Mountains => Terrain::Stone,
+            Biome::Beach => Terrain::Sand,
+        }
+    }
+
+    /// Terrain and block of a tile of this biome at the given height.
+    pub fn tile(&self, height: f64, rng: &mut StdRng) -> (Terrain, Option<BlockKind>) {
+        if height < -25.0 {
+            return (Terrain::DeepWater, None);
+        } else if height < 0.0 {
+            // lakes freeze in the cold
+            return match self {
+                Biome::Tundra => (Terrain::Ice, None),
+                _ => (Terrain::Water, None),
+            };
+        }
+        match self {
+            Biome::Mountains if height >= 40.0 => (Terrain::Stone, Some(Rock::generate(rng))),
+            Biome::Mountains => (Terrain::Stone, None),
+            // plains only grow things away from the shore
+            Biome::Plains if height < 10.0 => (Terrain::Grass, None),
+            Biome::Swamp if height < 6.0 => (Terrain::Water, None),
+            _ => {
+                let ground = self.ground();
+                let (tree, stones, sticks, grass, bush) = self.densities();
+                if rng.gen_ratio(tree, 1000) {
+                    (ground, Some(Tree::generate(rng)))
+                } else if rng.gen_ratio(stones, 1000) {
+                    (ground, Some(Stones::generate(rng)))
+                } else if rng.gen_ratio(sticks, 1000) {
+                    (ground, Some(Sticks::generate(rng)))
+                } else if rng.gen_ratio(grass, 1000) {
+                    (ground, Some(GrassTuft::generate(rng)))
+                } else if rng.gen_ratio(bush, 1000) {
+                    (ground, Some(Bush::generate(rng)))
+                } else {
+                    (ground, None)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use tui::{text::Span, style::{Style, Color}};
+use crate::items::{ItemKind, berries::Berries};
+use rand::{rngs::StdRng, Rng};
+use super::{Block, BlockKind};
+
+/// Picked by hand for berries until there are none left.
+pub struct Bush {
+    life: u32,
+}
+
+impl Block for Bush {
+    const XP: u32 = 1;
+
+    fn generate(rng: &mut StdRng) -> BlockKind {
+        BlockKind::Bush(
+            Self {
+                life: rng.gen_range(1..=3)
+            }
+        )
+    }
+
+    fn shape<'a>(&self) -> tui::text::Span<'a> {
+        Span::styled("♣", Style::default().fg(Color::Rgb(170, 40, 90)))
+    }
+
+    fn collect(&mut self) -> ItemKind {
+        if self.life > 0 {
+            self.life -= 1;
+        }
+        ItemKind::Berries(Berries::new(2))
+    }
+
+    fn is_compatible_tool(_item: &ItemKind) -> bool {
+        true
+    }
+
+    fn is_destroyed(&self) -> bool {
+        self.life == 0
+    }
+
+    fn life(&self) -> u32 {
+        self.life
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life;
+    }
+}
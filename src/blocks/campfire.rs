@@ -0,0 +1,43 @@
+use tui::{text::Span, style::{Style, Color}};
+use crate::items::{ItemKind, wood::Wood};
+use rand::rngs::StdRng;
+use super::{Block, BlockKind};
+
+/// Left burning in camps, raw meat is cooked on it.
+pub struct Campfire {
+    life: u32,
+}
+
+impl Block for Campfire {
+    fn generate(_rng: &mut StdRng) -> BlockKind {
+        BlockKind::Campfire(
+            Self {
+                life: 1
+            }
+        )
+    }
+
+    fn shape<'a>(&self) -> tui::text::Span<'a> {
+        Span::styled("ʌ", Style::default().fg(Color::Rgb(247, 138, 7)))
+    }
+
+    fn collect(&mut self) -> ItemKind {
+        ItemKind::Wood(Wood::new(1))
+    }
+
+    fn is_compatible_tool(_item: &ItemKind) -> bool {
+        false
+    }
+
+    fn is_destroyed(&self) -> bool {
+        self.life == 0
+    }
+
+    fn life(&self) -> u32 {
+        self.life
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life;
+    }
+}
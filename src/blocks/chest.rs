@@ -0,0 +1,64 @@
+use tui::{text::Span, style::{Style, Color}};
+use crate::items::{ItemKind, gold::Gold, iron::Iron, coal::Coal, stick::Stick};
+use rand::{rngs::StdRng, Rng};
+use super::{Block, BlockKind};
+
+/// Left behind in structures, opening it gives the loot inside.
+pub struct Chest {
+    life: u32,
+    loot: u8,
+}
+
+impl Chest {
+    /// Which of the loots is inside.
+    pub fn loot(&self) -> u8 {
+        self.loot
+    }
+
+    pub fn set_loot(&mut self, loot: u8) {
+        self.loot = loot;
+    }
+}
+
+impl Block for Chest {
+    const XP: u32 = 4;
+
+    fn generate(rng: &mut StdRng) -> BlockKind {
+        BlockKind::Chest(
+            Self {
+                life: 1,
+                loot: rng.gen_range(0..4)
+            }
+        )
+    }
+
+    fn shape<'a>(&self) -> tui::text::Span<'a> {
+        Span::styled("▣", Style::default().fg(Color::Rgb(181, 130, 60)))
+    }
+
+    fn collect(&mut self) -> ItemKind {
+        self.life = 0;
+        match self.loot {
+            0 => ItemKind::Gold(Gold::new(3)),
+            1 => ItemKind::Iron(Iron::new(4)),
+            2 => ItemKind::Coal(Coal::new(6)),
+            _ => ItemKind::Stick(Stick::new(8)),
+        }
+    }
+
+    fn is_compatible_tool(_item: &ItemKind) -> bool {
+        true
+    }
+
+    fn is_destroyed(&self) -> bool {
+        self.life == 0
+    }
+
+    fn life(&self) -> u32 {
+        self.life
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life;
+    }
+}
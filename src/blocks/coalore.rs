@@ -1,5 +1,6 @@
 use tui::{text::Span, style::{Style, Color}};
 use crate::items::{ItemKind, coal::Coal};
+use rand::rngs::StdRng;
 use super::{Block, BlockKind};
 
 pub struct CoalOre {
@@ -7,7 +8,9 @@ pub struct CoalOre {
 }
 
 impl Block for CoalOre {
-    fn generate() -> BlockKind {
+    const XP: u32 = 3;
+
+    fn generate(_rng: &mut StdRng) -> BlockKind {
         BlockKind::CoalOre(
             Self {
                 life: 15
@@ -25,13 +28,18 @@ impl Block for CoalOre {
     }
 
     fn is_compatible_tool(item: &ItemKind) -> bool {
-        match item {
-            ItemKind::Pickaxe(_) => true,
-            _ => false
-        }
+        matches!(item, ItemKind::Pickaxe(_))
     }
 
     fn is_destroyed(&self) -> bool {
         self.life == 0
     }
+
+    fn life(&self) -> u32 {
+        self.life
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life;
+    }
 }
@@ -1,13 +1,19 @@
 use tui::{text::Span, style::{Style, Color}};
-use crate::items::{ItemKind, gold::Gold};
+use crate::items::{ItemKind, gold::Gold, tier::{Tier, Tool}};
+use rand::rngs::StdRng;
 use super::{Block, BlockKind};
 
+/// Gold veins are too hard for anything below an iron pickaxe.
+const HARDNESS: Tier = Tier::Iron;
+
 pub struct GoldOre {
     life: u32,
 }
 
 impl Block for GoldOre {
-    fn generate() -> BlockKind {
+    const XP: u32 = 10;
+
+    fn generate(_rng: &mut StdRng) -> BlockKind {
         BlockKind::GoldOre(
             Self {
                 life: 15
@@ -26,7 +32,7 @@ impl Block for GoldOre {
 
     fn is_compatible_tool(item: &ItemKind) -> bool {
         match item {
-            ItemKind::Pickaxe(_) => true,
+            ItemKind::Pickaxe(pickaxe) => pickaxe.tier() >= HARDNESS,
             _ => false
         }
     }
@@ -34,4 +40,12 @@ impl Block for GoldOre {
     fn is_destroyed(&self) -> bool {
         self.life == 0
     }
+
+    fn life(&self) -> u32 {
+        self.life
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life;
+    }
 }
@@ -1,5 +1,6 @@
 use tui::{text::Span, style::Style};
 use crate::items::{ItemKind, grass::Grass};
+use rand::rngs::StdRng;
 use super::{Block, BlockKind};
 
 pub struct GrassTuft {
@@ -7,7 +8,7 @@ pub struct GrassTuft {
 }
 
 impl Block for GrassTuft {
-    fn generate() -> BlockKind {
+    fn generate(_rng: &mut StdRng) -> BlockKind {
         BlockKind::Grass(
             Self {
                 life: 1
@@ -31,4 +32,12 @@ impl Block for GrassTuft {
     fn is_destroyed(&self) -> bool {
         self.life == 0
     }
+
+    fn life(&self) -> u32 {
+        self.life
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life;
+    }
 }
@@ -0,0 +1,43 @@
+use tui::{text::Span, style::{Style, Color}};
+use crate::items::{ItemKind, stone::Stone};
+use rand::rngs::StdRng;
+use super::{Block, BlockKind};
+
+/// Stairs dug in stone, stepping into it leads down to the caves.
+pub struct Hole {
+    life: u32,
+}
+
+impl Block for Hole {
+    fn generate(_rng: &mut StdRng) -> BlockKind {
+        BlockKind::Hole(
+            Self {
+                life: 1
+            }
+        )
+    }
+
+    fn shape<'a>(&self) -> tui::text::Span<'a> {
+        Span::styled("▼", Style::default().fg(Color::Black).bg(Color::Rgb(84, 106, 78)))
+    }
+
+    fn collect(&mut self) -> ItemKind {
+        ItemKind::Stone(Stone::new(1))
+    }
+
+    fn is_compatible_tool(_item: &ItemKind) -> bool {
+        false
+    }
+
+    fn is_destroyed(&self) -> bool {
+        self.life == 0
+    }
+
+    fn life(&self) -> u32 {
+        self.life
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life;
+    }
+}
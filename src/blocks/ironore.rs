@@ -1,5 +1,6 @@
 use tui::{text::Span, style::{Style, Color}};
-use crate::items::{ItemKind, wood::Wood};
+use crate::items::{ItemKind, iron::Iron};
+use rand::rngs::StdRng;
 use super::{Block, BlockKind};
 
 pub struct IronOre {
@@ -7,7 +8,9 @@ pub struct IronOre {
 }
 
 impl Block for IronOre {
-    fn generate() -> BlockKind {
+    const XP: u32 = 6;
+
+    fn generate(_rng: &mut StdRng) -> BlockKind {
         BlockKind::IronOre(
             Self {
                 life: 15
@@ -21,17 +24,22 @@ impl Block for IronOre {
 
     fn collect(&mut self) -> ItemKind {
         self.life -= 1;
-        ItemKind::Wood(Wood::new(2))
+        ItemKind::Iron(Iron::new(2))
     }
 
     fn is_compatible_tool(item: &ItemKind) -> bool {
-        match item {
-            ItemKind::Pickaxe(_) => true,
-            _ => false
-        }
+        matches!(item, ItemKind::Pickaxe(_))
     }
 
     fn is_destroyed(&self) -> bool {
         self.life == 0
     }
+
+    fn life(&self) -> u32 {
+        self.life
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life;
+    }
 }
@@ -0,0 +1,43 @@
+use tui::{text::Span, style::{Style, Color}};
+use crate::items::{ItemKind, stone::Stone};
+use rand::rngs::StdRng;
+use super::{Block, BlockKind};
+
+/// Stairs at the bottom of a hole, stepping into it leads back to the surface.
+pub struct Ladder {
+    life: u32,
+}
+
+impl Block for Ladder {
+    fn generate(_rng: &mut StdRng) -> BlockKind {
+        BlockKind::Ladder(
+            Self {
+                life: 1
+            }
+        )
+    }
+
+    fn shape<'a>(&self) -> tui::text::Span<'a> {
+        Span::styled("▲", Style::default().fg(Color::White).bg(Color::Rgb(48, 42, 40)))
+    }
+
+    fn collect(&mut self) -> ItemKind {
+        ItemKind::Stone(Stone::new(1))
+    }
+
+    fn is_compatible_tool(_item: &ItemKind) -> bool {
+        false
+    }
+
+    fn is_destroyed(&self) -> bool {
+        self.life == 0
+    }
+
+    fn life(&self) -> u32 {
+        self.life
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life;
+    }
+}
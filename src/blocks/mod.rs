@@ -6,11 +6,19 @@ pub mod goldore;
 pub mod coalore;
 pub mod rock;
 pub mod sticks;
+pub mod hole;
+pub mod ladder;
+pub mod chest;
+pub mod bush;
+pub mod campfire;
+pub mod grave;
 
+use rand::rngs::StdRng;
+use std::mem::discriminant;
 use tui::text::Span;
 use crate::items::ItemKind;
 
-use self::{tree::Tree, rock::Rock, grasstuft::GrassTuft, goldore::GoldOre, ironore::IronOre, coalore::CoalOre, stones::Stones, sticks::Sticks};
+use self::{tree::Tree, rock::Rock, grasstuft::GrassTuft, goldore::GoldOre, ironore::IronOre, coalore::CoalOre, stones::Stones, sticks::Sticks, hole::Hole, ladder::Ladder, chest::Chest, bush::Bush, campfire::Campfire, grave::Grave};
 
 pub enum BlockKind {
     Tree(Tree),
@@ -20,7 +28,13 @@ pub enum BlockKind {
     CoalOre(CoalOre),
     Stones(Stones),
     Rock(Rock),
-    Sticks(Sticks)
+    Sticks(Sticks),
+    Hole(Hole),
+    Ladder(Ladder),
+    Chest(Chest),
+    Bush(Bush),
+    Campfire(Campfire),
+    Grave(Grave)
 }
 
 impl BlockKind {
@@ -33,7 +47,13 @@ impl BlockKind {
             BlockKind::CoalOre(b) => b.shape(),
             BlockKind::Stones(b) => b.shape(),
             BlockKind::Rock(b) => b.shape(),
-            BlockKind::Sticks(b) => b.shape()
+            BlockKind::Sticks(b) => b.shape(),
+            BlockKind::Hole(b) => b.shape(),
+            BlockKind::Ladder(b) => b.shape(),
+            BlockKind::Chest(b) => b.shape(),
+            BlockKind::Bush(b) => b.shape(),
+            BlockKind::Campfire(b) => b.shape(),
+            BlockKind::Grave(b) => b.shape()
         }
     }
     
@@ -46,7 +66,13 @@ impl BlockKind {
             BlockKind::CoalOre(b) => b.collect(),
             BlockKind::Stones(b) => b.collect(),
             BlockKind::Rock(b) => b.collect(),
-            BlockKind::Sticks(b) => b.collect()
+            BlockKind::Sticks(b) => b.collect(),
+            BlockKind::Hole(b) => b.collect(),
+            BlockKind::Ladder(b) => b.collect(),
+            BlockKind::Chest(b) => b.collect(),
+            BlockKind::Bush(b) => b.collect(),
+            BlockKind::Campfire(b) => b.collect(),
+            BlockKind::Grave(b) => b.collect()
         }
     }
 
@@ -59,10 +85,92 @@ impl BlockKind {
             BlockKind::CoalOre(b) => b.is_destroyed(),
             BlockKind::Stones(b) => b.is_destroyed(),
             BlockKind::Rock(b) => b.is_destroyed(),
-            BlockKind::Sticks(b) => b.is_destroyed()
+            BlockKind::Sticks(b) => b.is_destroyed(),
+            BlockKind::Hole(b) => b.is_destroyed(),
+            BlockKind::Ladder(b) => b.is_destroyed(),
+            BlockKind::Chest(b) => b.is_destroyed(),
+            BlockKind::Bush(b) => b.is_destroyed(),
+            BlockKind::Campfire(b) => b.is_destroyed(),
+            BlockKind::Grave(b) => b.is_destroyed()
         }
     }
 
+    pub fn life(&self) -> u32 {
+        match self {
+            BlockKind::Tree(b) => b.life(),
+            BlockKind::Grass(b) => b.life(),
+            BlockKind::GoldOre(b) => b.life(),
+            BlockKind::IronOre(b) => b.life(),
+            BlockKind::CoalOre(b) => b.life(),
+            BlockKind::Stones(b) => b.life(),
+            BlockKind::Rock(b) => b.life(),
+            BlockKind::Sticks(b) => b.life(),
+            BlockKind::Hole(b) => b.life(),
+            BlockKind::Ladder(b) => b.life(),
+            BlockKind::Chest(b) => b.life(),
+            BlockKind::Bush(b) => b.life(),
+            BlockKind::Campfire(b) => b.life(),
+            BlockKind::Grave(b) => b.life()
+        }
+    }
+
+    pub fn set_life(&mut self, life: u32) {
+        match self {
+            BlockKind::Tree(b) => b.set_life(life),
+            BlockKind::Grass(b) => b.set_life(life),
+            BlockKind::GoldOre(b) => b.set_life(life),
+            BlockKind::IronOre(b) => b.set_life(life),
+            BlockKind::CoalOre(b) => b.set_life(life),
+            BlockKind::Stones(b) => b.set_life(life),
+            BlockKind::Rock(b) => b.set_life(life),
+            BlockKind::Sticks(b) => b.set_life(life),
+            BlockKind::Hole(b) => b.set_life(life),
+            BlockKind::Ladder(b) => b.set_life(life),
+            BlockKind::Chest(b) => b.set_life(life),
+            BlockKind::Bush(b) => b.set_life(life),
+            BlockKind::Campfire(b) => b.set_life(life),
+            BlockKind::Grave(b) => b.set_life(life)
+        }
+    }
+
+    pub fn xp(&self) -> u32 {
+        match self {
+            BlockKind::Tree(_) => Tree::XP,
+            BlockKind::Grass(_) => GrassTuft::XP,
+            BlockKind::GoldOre(_) => GoldOre::XP,
+            BlockKind::IronOre(_) => IronOre::XP,
+            BlockKind::CoalOre(_) => CoalOre::XP,
+            BlockKind::Stones(_) => Stones::XP,
+            BlockKind::Rock(_) => Rock::XP,
+            BlockKind::Sticks(_) => Sticks::XP,
+            BlockKind::Hole(_) => Hole::XP,
+            BlockKind::Ladder(_) => Ladder::XP,
+            BlockKind::Chest(_) => Chest::XP,
+            BlockKind::Bush(_) => Bush::XP,
+            BlockKind::Campfire(_) => Campfire::XP,
+            BlockKind::Grave(_) => Grave::XP
+        }
+    }
+
+    /// What the block holds besides its life, only chests hold something.
+    pub fn contents(&self) -> Option<u8> {
+        match self {
+            BlockKind::Chest(b) => Some(b.loot()),
+            _ => None,
+        }
+    }
+
+    pub fn set_contents(&mut self, contents: u8) {
+        if let BlockKind::Chest(b) = self {
+            b.set_loot(contents);
+        }
+    }
+
+    /// Same kind of block with the same amount of life left and the same contents.
+    pub fn is_same(&self, other: &BlockKind) -> bool {
+        discriminant(self) == discriminant(other) && self.life() == other.life() && self.contents() == other.contents()
+    }
+
     pub fn is_compatible_tool(&self, item: &ItemKind) -> bool {
         match &self {
             BlockKind::Tree(_) => Tree::is_compatible_tool(item),
@@ -72,15 +180,26 @@ impl BlockKind {
             BlockKind::CoalOre(_) => CoalOre::is_compatible_tool(item),
             BlockKind::Stones(_) => Stones::is_compatible_tool(item),
             BlockKind::Rock(_) => Rock::is_compatible_tool(item),
-            BlockKind::Sticks(_) => Sticks::is_compatible_tool(item)
+            BlockKind::Sticks(_) => Sticks::is_compatible_tool(item),
+            BlockKind::Hole(_) => Hole::is_compatible_tool(item),
+            BlockKind::Ladder(_) => Ladder::is_compatible_tool(item),
+            BlockKind::Chest(_) => Chest::is_compatible_tool(item),
+            BlockKind::Bush(_) => Bush::is_compatible_tool(item),
+            BlockKind::Campfire(_) => Campfire::is_compatible_tool(item),
+            BlockKind::Grave(_) => Grave::is_compatible_tool(item)
         }
     }
 }
 
 pub trait Block {
-    fn generate() -> BlockKind;
+    /// Experience given to the player for breaking the block.
+    const XP: u32 = 0;
+
+    fn generate(rng: &mut StdRng) -> BlockKind;
     fn collect(&mut self) -> ItemKind;
     fn shape<'a>(&self) -> Span<'a>;
     fn is_compatible_tool(item: &ItemKind) -> bool;
     fn is_destroyed(&self) -> bool;
+    fn life(&self) -> u32;
+    fn set_life(&mut self, life: u32);
 }
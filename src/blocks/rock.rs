@@ -1,5 +1,6 @@
 use tui::{text::Span, style::{Color, Style}};
 use crate::items::{ItemKind, stone::Stone};
+use rand::rngs::StdRng;
 use super::{Block, BlockKind};
 
 pub struct Rock {
@@ -7,7 +8,9 @@ pub struct Rock {
 }
 
 impl Block for Rock {
-    fn generate() -> BlockKind {
+    const XP: u32 = 1;
+
+    fn generate(_rng: &mut StdRng) -> BlockKind {
         BlockKind::Rock(
             Self {
                 life: 15
@@ -28,14 +31,18 @@ impl Block for Rock {
     }
 
     fn is_compatible_tool(tool: &ItemKind) -> bool {
-        if let ItemKind::Pickaxe(_) = tool {
-            true
-        } else {
-            false
-        }
+        matches!(tool, ItemKind::Pickaxe(_))
     }
 
     fn is_destroyed(&self) -> bool {
         self.life == 0
     }
+
+    fn life(&self) -> u32 {
+        self.life as u32
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life as u8;
+    }
 }
@@ -1,5 +1,6 @@
 use tui::{text::Span, style::{Style, Color}};
 use crate::items::{ItemKind, stick::Stick};
+use rand::rngs::StdRng;
 use super::{Block, BlockKind};
 
 pub struct Sticks {
@@ -7,7 +8,7 @@ pub struct Sticks {
 }
 
 impl Block for Sticks {
-    fn generate() -> BlockKind {
+    fn generate(_rng: &mut StdRng) -> BlockKind {
         BlockKind::Sticks(
             Self {
                 life: 1
@@ -27,14 +28,18 @@ impl Block for Sticks {
     }
 
     fn is_compatible_tool(item: &ItemKind) -> bool {
-        match item {
-            ItemKind::Hand(_) => true,
-            ItemKind::Axe(_) => true,
-            _ => false
-        }
+        matches!(item, ItemKind::Hand(_) | ItemKind::Axe(_))
     }
 
     fn is_destroyed(&self) -> bool {
         self.life == 0
     }
+
+    fn life(&self) -> u32 {
+        self.life as u32
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life as u8;
+    }
 }
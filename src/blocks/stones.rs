@@ -1,4 +1,4 @@
-use rand::{thread_rng, Rng};
+use rand::{rngs::StdRng, Rng};
 use tui::{text::Span, style::{Style, Color}};
 use crate::items::{ItemKind, stone::Stone};
 use super::{Block, BlockKind};
@@ -7,6 +7,12 @@ pub struct Stones {
     life: u32,
 }
 
+impl Default for Stones {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Stones {
     pub fn new() -> Self {
         Self {
@@ -16,10 +22,12 @@ impl Stones {
 }
 
 impl Block for Stones {
-    fn generate() -> BlockKind {
+    const XP: u32 = 1;
+
+    fn generate(rng: &mut StdRng) -> BlockKind {
         BlockKind::Stones(
             Self {
-                life: thread_rng().gen_range(1..=4)
+                life: rng.gen_range(1..=4)
             }
         )
     }
@@ -42,14 +50,18 @@ impl Block for Stones {
     }
 
     fn is_compatible_tool(item: &ItemKind) -> bool {
-        match item {
-            ItemKind::Hand(_) => true,
-            ItemKind::Pickaxe(_) => true,
-            _ => false,
-        }
+        matches!(item, ItemKind::Hand(_) | ItemKind::Pickaxe(_))
     }
 
     fn is_destroyed(&self) -> bool {
         self.life == 0
     }
+
+    fn life(&self) -> u32 {
+        self.life
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life;
+    }
 }
@@ -1,5 +1,6 @@
 use tui::{text::Span, style::Style};
 use crate::items::{ItemKind, wood::Wood};
+use rand::rngs::StdRng;
 use super::{Block, BlockKind};
 
 pub struct Tree {
@@ -7,7 +8,9 @@ pub struct Tree {
 }
 
 impl Block for Tree {
-    fn generate() -> BlockKind {
+    const XP: u32 = 2;
+
+    fn generate(_rng: &mut StdRng) -> BlockKind {
         BlockKind::Tree(
             Tree {
                 life: 10
@@ -25,13 +28,18 @@ impl Block for Tree {
     }
 
     fn is_compatible_tool(item: &ItemKind) -> bool {
-        match item {
-            ItemKind::Axe(_) => true,
-            _ => false
-        }
+        matches!(item, ItemKind::Axe(_))
     }
 
     fn is_destroyed(&self) -> bool {
         self.life == 0
     }
+
+    fn life(&self) -> u32 {
+        self.life as u32
+    }
+
+    fn set_life(&mut self, life: u32) {
+        self.life = life as u8;
+    }
 }
@@ -1,90 +1,316 @@
 use std::ops::{Index, IndexMut};
 
-use perlin2d::PerlinNoise2D;
-use rand::{thread_rng, Rng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use tui::{style::{Color, Style}, text::Span, widgets::canvas::Context};
 
-use crate::{blocks::{BlockKind, stones::Stones, tree::Tree, Block, sticks::Sticks, rock::Rock}, entities::{EntityKind, snake::Snake, ovis::Ovis, scorpy::Scorpy}};
+use crate::{biome::WorldNoise, blocks::{BlockKind, Block, coalore::CoalOre, ironore::IronOre, goldore::GoldOre, rock::Rock}, entities::{EntityKind, snake::SNAKE, ovis::OVIS, scorpy::SCORPY, golem::GOLEM, crawler::CRAWLER}, layer::Depth, structure::Structure, river::carve_rivers};
 
 pub const CHUNK_SIZE: i64 = 16;
 
+/// Ore veins seeded in stone: (veins tried per chunk, chance of each try in percent, longest vein)
+const COAL_VEINS: (u32, u32, u32) = (4, 50, 8);
+const IRON_VEINS: (u32, u32, u32) = (3, 35, 6);
+const GOLD_VEINS: (u32, u32, u32) = (2, 15, 4);
+const CAVE_COAL_VEINS: (u32, u32, u32) = (6, 70, 10);
+const CAVE_IRON_VEINS: (u32, u32, u32) = (5, 60, 8);
+const CAVE_GOLD_VEINS: (u32, u32, u32) = (3, 40, 6);
+
+/// Smoothing passes of the cellular automaton carving the caves.
+const CAVE_STEPS: i64 = 4;
+/// Chance in percent for a cave tile to start as a wall.
+const CAVE_FILL: u64 = 45;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Terrain {
     DeepWater,
     Water,
     Grass,
-    Stone
+    Stone,
+    Sand,
+    Snow,
+    Ice,
+    Mud,
+    Moss,
+    Cave
 }
 
 impl Terrain {
+    pub const ALL: [Terrain; 10] = [
+        Terrain::DeepWater,
+        Terrain::Water,
+        Terrain::Grass,
+        Terrain::Stone,
+        Terrain::Sand,
+        Terrain::Snow,
+        Terrain::Ice,
+        Terrain::Mud,
+        Terrain::Moss,
+        Terrain::Cave,
+    ];
+
     pub fn color(&self) -> Color {
         match self {
             Terrain::DeepWater => Color::Rgb(54, 181, 201),
             Terrain::Water => Color::Rgb(54, 201, 148),
             Terrain::Grass => Color::Rgb(70, 201, 54),
             Terrain::Stone => Color::Rgb(84, 106, 78),
+            Terrain::Sand => Color::Rgb(232, 214, 141),
+            Terrain::Snow => Color::Rgb(240, 244, 248),
+            Terrain::Ice => Color::Rgb(170, 220, 235),
+            Terrain::Mud => Color::Rgb(94, 110, 60),
+            Terrain::Moss => Color::Rgb(40, 140, 40),
+            Terrain::Cave => Color::Rgb(48, 42, 40),
         }
     }
 
+    pub fn is_water(&self) -> bool {
+        matches!(self, Terrain::Water | Terrain::DeepWater)
+    }
+
     pub fn style(&self) -> Style {
         Style::default().bg(self.color())
     }
 
     pub fn span<'a>(&self) -> Span<'a> {
-        match self {
-            Terrain::DeepWater => Span::styled(" ", self.style()),
-            Terrain::Water => Span::styled(" ", self.style()),
-            Terrain::Grass => Span::styled(" ", self.style()),
-            Terrain::Stone => Span::styled(" ", self.style()),
-        }
+        Span::styled(" ", self.style())
     }
 
-    pub fn random_entity(&self, x: i64, y: i64) -> Option<EntityKind> {
+    pub fn random_entity(&self, x: i64, y: i64, rng: &mut StdRng) -> Option<EntityKind> {
         match self {
-            Terrain::Grass => match thread_rng().gen_range(1..=2) {
-                1 => Some(EntityKind::Snake(Snake::new(x, y))),
-                _ => Some(EntityKind::Ovis(Ovis::new(x, y))),
+            Terrain::Grass => match rng.gen_range(1..=2) {
+                1 => Some(SNAKE.spawn(x, y)),
+                _ => Some(OVIS.spawn(x, y)),
+            },
+            Terrain::Moss => match rng.gen_range(1..=3) {
+                1 => Some(SNAKE.spawn(x, y)),
+                _ => Some(OVIS.spawn(x, y)),
+            },
+            Terrain::Sand => match rng.gen_range(1..=3) {
+                1 => Some(SNAKE.spawn(x, y)),
+                _ => Some(SCORPY.spawn(x, y)),
             },
-            Terrain::Stone => Some(EntityKind::Scorpy(Scorpy::new(x, y))),
+            Terrain::Stone => Some(SCORPY.spawn(x, y)),
+            Terrain::Snow => Some(OVIS.spawn(x, y)),
+            Terrain::Mud => Some(SNAKE.spawn(x, y)),
+            Terrain::Cave => match rng.gen_range(1..=3) {
+                1 => Some(GOLEM.spawn(x, y)),
+                _ => Some(CRAWLER.spawn(x, y)),
+            },
+            Terrain::Ice => None,
             Terrain::Water => None,
             Terrain::DeepWater => None,
         }
     }
 }
 
-pub struct Chunk(pub i64, pub i64, pub Vec<(Terrain, Option<BlockKind>)>);
+/// Split world coordinates into the coordinates of their chunk and
+/// the position of the tile inside that chunk.
+pub fn chunk_coords(x: i64, y: i64) -> ((i64, i64), (usize, usize)) {
+    (
+        (x.div_euclid(CHUNK_SIZE), y.div_euclid(CHUNK_SIZE)),
+        (x.rem_euclid(CHUNK_SIZE) as usize, y.rem_euclid(CHUNK_SIZE) as usize),
+    )
+}
+
+/// Mix the world seed with a pair of coordinates into a reproducible hash.
+pub fn mix(seed: u32, a: i64, b: i64) -> u64 {
+    let mut h = seed as u64;
+    for v in [a as u64, b as u64] {
+        h ^= v.wrapping_add(0x9e3779b97f4a7c15).wrapping_add(h << 6).wrapping_add(h >> 2);
+        h = (h ^ (h >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        h = (h ^ (h >> 27)).wrapping_mul(0x94d049bb133111eb);
+        h ^= h >> 31;
+    }
+    h
+}
+
+/// Mix the world seed with chunk coordinates so that each chunk
+/// gets its own reproducible random generator.
+pub fn chunk_rng(seed: u32, col: i64, row: i64) -> StdRng {
+    StdRng::seed_from_u64(mix(seed, col, row))
+}
+
+/// Blocks of a chunk that differ from what the world seed generates.
+/// Only these are kept once the chunk is unloaded.
+#[derive(Default)]
+pub struct ChunkDelta(pub Vec<(usize, usize, Option<BlockKind>)>);
+
+impl ChunkDelta {
+    pub fn set(&mut self, i: usize, j: usize, block: Option<BlockKind>) {
+        match self.0.iter_mut().find(|(a, b, _)| (*a, *b) == (i, j)) {
+            Some(tile) => tile.2 = block,
+            None => self.0.push((i, j, block)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// The last field tells if a block of the chunk may have been changed
+/// since it was generated.
+pub struct Chunk(pub i64, pub i64, pub Vec<(Terrain, Option<BlockKind>)>, pub bool);
 
 impl Chunk {
-    pub fn new(col: i64, row: i64, perlin: &PerlinNoise2D) -> Self {
+    pub fn new(col: i64, row: i64, noise: &WorldNoise, seed: u32) -> Self {
+        let mut rng = chunk_rng(seed, col, row);
         let mut terrain = Vec::new();
         for i in 0..CHUNK_SIZE {
             for j in 0..CHUNK_SIZE {
                 let x = (col*CHUNK_SIZE + i) as f64;
                 let y = (row*CHUNK_SIZE + j) as f64;
-                let value = perlin.get_noise(x, y);
-                if value >= 40.0 {
-                    terrain.push((Terrain::Stone, Some(Rock::generate())))
-                } else if value >= 30.0 {
-                    terrain.push((Terrain::Stone, None))
-                } else if value >= 10.0 {
-                    if thread_rng().gen_ratio(1, 15) {
-                        terrain.push((Terrain::Grass, Some(Tree::generate())))
-                    } else if thread_rng().gen_ratio(1, 100) {
-                        terrain.push((Terrain::Grass, Some(Stones::generate())))
-                    } else if thread_rng().gen_ratio(1, 100) {
-                        terrain.push((Terrain::Grass, Some(Sticks::generate())))
-                    } else {
-                        terrain.push((Terrain::Grass, None))
+                let height = noise.height.get_noise(x, y);
+                terrain.push(noise.biome(x, y).tile(height, &mut rng));
+            }
+        }
+        let mut chunk = Self(col, row, terrain, false);
+        carve_rivers(&mut chunk, noise, seed);
+        let in_stone = |tile: &(Terrain, Option<BlockKind>)| tile.0 == Terrain::Stone;
+        chunk.place_veins(&mut rng, COAL_VEINS, CoalOre::generate, in_stone);
+        chunk.place_veins(&mut rng, IRON_VEINS, IronOre::generate, in_stone);
+        chunk.place_veins(&mut rng, GOLD_VEINS, GoldOre::generate, in_stone);
+        chunk
+    }
+
+    /// Generate the chunk of the given level of the world.
+    pub fn generate(depth: Depth, col: i64, row: i64, noise: &WorldNoise, seed: u32) -> Self {
+        let mut chunk = match depth {
+            Depth::Surface => Chunk::new(col, row, noise, seed),
+            Depth::Caves => Chunk::cave(col, row, seed),
+        };
+        if let Some(structure) = Structure::of_chunk(depth, col, row, noise, seed) {
+            structure.stamp(&mut chunk);
+        }
+        chunk
+    }
+
+    /// Caves are carved by a cellular automaton. It runs on the chunk and a
+    /// margin around it, wide enough for the border tiles to come out the same
+    /// in every chunk that computes them, so caves continue across chunks.
+    pub fn cave(col: i64, row: i64, seed: u32) -> Self {
+        let cave_seed = seed.rotate_left(16);
+        let size = (CHUNK_SIZE + 2 * CAVE_STEPS) as usize;
+        let mut walls = vec![false; size * size];
+        for a in 0..size {
+            for b in 0..size {
+                let x = col*CHUNK_SIZE - CAVE_STEPS + a as i64;
+                let y = row*CHUNK_SIZE - CAVE_STEPS + b as i64;
+                walls[a * size + b] = mix(cave_seed, x, y) % 100 < CAVE_FILL;
+            }
+        }
+        for _ in 0..CAVE_STEPS {
+            let mut next = walls.clone();
+            for a in 1..size - 1 {
+                for b in 1..size - 1 {
+                    let mut neighbours = 0;
+                    for na in a - 1..=a + 1 {
+                        for nb in b - 1..=b + 1 {
+                            if walls[na * size + nb] { neighbours += 1 }
+                        }
                     }
-                } else if value >= 0.0 {
-                    terrain.push((Terrain::Grass, None))
-                } else if value >= -25.0 {
-                    terrain.push((Terrain::Water, None))
+                    next[a * size + b] = neighbours >= 5;
+                }
+            }
+            walls = next;
+        }
+
+        let mut rng = chunk_rng(cave_seed, col, row);
+        let mut terrain = Vec::new();
+        for i in 0..CHUNK_SIZE as usize {
+            for j in 0..CHUNK_SIZE as usize {
+                let a = i + CAVE_STEPS as usize;
+                let b = j + CAVE_STEPS as usize;
+                if walls[a * size + b] {
+                    terrain.push((Terrain::Cave, Some(Rock::generate(&mut rng))))
                 } else {
-                    terrain.push((Terrain::DeepWater, None))
+                    terrain.push((Terrain::Cave, None))
                 }
             }
         }
-        Self(col, row, terrain)
+        let mut chunk = Self(col, row, terrain, false);
+        let in_wall = |tile: &(Terrain, Option<BlockKind>)| tile.1.is_some();
+        chunk.place_veins(&mut rng, CAVE_COAL_VEINS, CoalOre::generate, in_wall);
+        chunk.place_veins(&mut rng, CAVE_IRON_VEINS, IronOre::generate, in_wall);
+        chunk.place_veins(&mut rng, CAVE_GOLD_VEINS, GoldOre::generate, in_wall);
+        chunk
+    }
+
+    /// Random walks starting on a `host` tile that turn host tiles into ore.
+    fn place_veins(
+        &mut self,
+        rng: &mut StdRng,
+        veins: (u32, u32, u32),
+        ore: fn(&mut StdRng) -> BlockKind,
+        host: fn(&(Terrain, Option<BlockKind>)) -> bool,
+    ) {
+        let (tries, chance, length) = veins;
+        let stone_tiles: Vec<usize> = self.2
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| host(tile))
+            .map(|(idx, _)| idx)
+            .collect();
+        for _ in 0..tries {
+            if stone_tiles.is_empty() || !rng.gen_ratio(chance, 100) {
+                continue;
+            }
+            let start = stone_tiles[rng.gen_range(0..stone_tiles.len())];
+            let size = CHUNK_SIZE as usize;
+            let (mut i, mut j) = (start / size, start % size);
+            for _ in 0..rng.gen_range(1..=length) {
+                if host(&self[(i, j)]) {
+                    self[(i, j)].1 = Some(ore(rng));
+                }
+                match rng.gen_range(0..4) {
+                    0 => i = (i + 1).min(size - 1),
+                    1 => i = i.saturating_sub(1),
+                    2 => j = (j + 1).min(size - 1),
+                    _ => j = j.saturating_sub(1),
+                }
+            }
+        }
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.3
+    }
+
+    pub fn set_modified(&mut self) {
+        self.3 = true;
+    }
+
+    /// Tiles whose block is not the one of the freshly generated `baseline`.
+    pub fn changed_tiles(&self, baseline: &Chunk) -> Vec<(usize, usize)> {
+        let mut tiles = Vec::new();
+        for i in 0..CHUNK_SIZE as usize {
+            for j in 0..CHUNK_SIZE as usize {
+                let same = match (&self[(i, j)].1, &baseline[(i, j)].1) {
+                    (None, None) => true,
+                    (Some(block), Some(base)) => block.is_same(base),
+                    _ => false,
+                };
+                if !same {
+                    tiles.push((i, j));
+                }
+            }
+        }
+        tiles
+    }
+
+    pub fn into_delta(mut self, baseline: &Chunk) -> ChunkDelta {
+        let mut delta = ChunkDelta::default();
+        for (i, j) in self.changed_tiles(baseline) {
+            delta.set(i, j, self[(i, j)].1.take());
+        }
+        delta
+    }
+
+    pub fn apply(&mut self, delta: ChunkDelta) {
+        for (i, j, block) in delta.0 {
+            self[(i, j)].1 = block;
+        }
+        self.set_modified();
     }
 
     pub fn draw(&self, ctx: &mut Context) {
@@ -102,32 +328,21 @@ impl Chunk {
     }
 
     pub fn average_terrain(&self) -> Terrain {
-        let mut counter = [0; 4];
-        for i in 0..CHUNK_SIZE {
-            for j in 0..CHUNK_SIZE {
-                match self[(i as usize, j as usize)].0 {
-                    Terrain::DeepWater => counter[0] += 1,
-                    Terrain::Water => counter[1] += 1,
-                    Terrain::Grass => counter[2] += 1,
-                    Terrain::Stone => counter[3] += 1,
-                }
+        let mut counter = [0; Terrain::ALL.len()];
+        for (terrain, _) in &self.2 {
+            if let Some(idx) = Terrain::ALL.iter().position(|t| t == terrain) {
+                counter[idx] += 1;
             }
         }
         let mut max = 0;
-        let mut idx = 0;
+        let mut idx = 2;
         for (i, c) in counter.iter().enumerate() {
             if c > &max {
                 idx = i;
                 max = *c;
             }
         }
-        match idx {
-            0 => Terrain::DeepWater,
-            1 => Terrain::Water,
-            2 => Terrain::Grass,
-            3 => Terrain::Stone,
-            _ => Terrain::Grass
-        }
+        Terrain::ALL[idx]
     }
 }
 
@@ -144,3 +359,84 @@ impl IndexMut<(usize, usize)> for Chunk {
         &mut self.2[i * (CHUNK_SIZE as usize) + j]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::chest::Chest;
+
+    fn same_tiles(a: &Chunk, b: &Chunk) -> bool {
+        a.2.iter().zip(&b.2).all(|((ta, ba), (tb, bb))| {
+            ta == tb && match (ba, bb) {
+                (Some(ba), Some(bb)) => ba.is_same(bb),
+                (None, None) => true,
+                _ => false,
+            }
+        })
+    }
+
+    #[test]
+    fn chunks_are_the_same_every_time_they_are_generated() {
+        let seed = 42;
+        let noise = WorldNoise::new(seed);
+        for (col, row) in [(0, 0), (3, -2), (-5, -7)] {
+            let first = Chunk::new(col, row, &noise, seed);
+            let second = Chunk::new(col, row, &WorldNoise::new(seed), seed);
+            assert!(same_tiles(&first, &second), "chunk {col} {row} changed");
+        }
+    }
+
+    #[test]
+    fn chunks_differ_between_seeds() {
+        let differ = [(0, 0), (1, 1), (-2, 3)].into_iter().any(|(col, row)| {
+            let a = Chunk::new(col, row, &WorldNoise::new(1), 1);
+            let b = Chunk::new(col, row, &WorldNoise::new(2), 2);
+            !same_tiles(&a, &b)
+        });
+        assert!(differ);
+    }
+
+    #[test]
+    fn a_chunk_delta_rebuilds_the_changed_chunk() {
+        let seed = 42;
+        let noise = WorldNoise::new(seed);
+        let change = |chunk: &mut Chunk| {
+            let mut rng = StdRng::seed_from_u64(0);
+            let mut chest = Chest::generate(&mut rng);
+            chest.set_contents(1);
+            chunk[(0, 0)].1 = None;
+            chunk[(4, 5)].1 = Some(Rock::generate(&mut rng));
+            chunk[(6, 7)].1 = Some(chest);
+        };
+        let mut changed = Chunk::generate(Depth::Surface, 2, 3, &noise, seed);
+        change(&mut changed);
+        let baseline = Chunk::generate(Depth::Surface, 2, 3, &noise, seed);
+        let mut rebuilt = Chunk::generate(Depth::Surface, 2, 3, &noise, seed);
+        rebuilt.apply(changed.into_delta(&baseline));
+
+        let mut expected = Chunk::generate(Depth::Surface, 2, 3, &noise, seed);
+        change(&mut expected);
+        assert!(same_tiles(&rebuilt, &expected));
+    }
+
+    #[test]
+    fn chests_with_other_contents_are_changes() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (mut a, mut b) = (Chest::generate(&mut rng), Chest::generate(&mut rng));
+        a.set_contents(0);
+        b.set_contents(2);
+        assert!(!a.is_same(&b));
+        b.set_contents(0);
+        assert!(a.is_same(&b));
+    }
+
+    #[test]
+    fn chunk_coords_split_negative_multiples_of_the_chunk_size() {
+        assert_eq!(chunk_coords(-17, 0), ((-2, 0), (15, 0)));
+        assert_eq!(chunk_coords(-16, 0), ((-1, 0), (0, 0)));
+        assert_eq!(chunk_coords(-1, 0), ((-1, 0), (15, 0)));
+        assert_eq!(chunk_coords(0, 0), ((0, 0), (0, 0)));
+        assert_eq!(chunk_coords(15, 0), ((0, 0), (15, 0)));
+        assert_eq!(chunk_coords(0, -16), ((0, -1), (0, 0)));
+    }
+}
@@ -0,0 +1,166 @@
+use tui::{
+    style::{Color, Style},
+    text::Span,
+};
+
+/// Ticks in a second of game time.
+pub const TICKS_PER_SECOND: u16 = 20;
+
+/// Something lasting a while on the player or a creature.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Effect {
+    Poison,
+    Burning,
+    Slow,
+    Regeneration,
+}
+
+impl Effect {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Effect::Poison => "poisoned",
+            Effect::Burning => "burning",
+            Effect::Slow => "slowed",
+            Effect::Regeneration => "regenerating",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Effect::Poison => Color::Green,
+            Effect::Burning => Color::Rgb(247, 138, 7),
+            Effect::Slow => Color::Blue,
+            Effect::Regeneration => Color::Magenta,
+        }
+    }
+
+    /// Ticks between two pulses and the life each of them gives,
+    /// `None` for effects leaving life alone.
+    fn pulse(&self) -> Option<(u16, i16)> {
+        match self {
+            Effect::Poison => Some((20, -2)),
+            Effect::Burning => Some((10, -3)),
+            Effect::Slow => None,
+            Effect::Regeneration => Some((10, 1)),
+        }
+    }
+}
+
+/// The effects currently on someone with the ticks they have left.
+#[derive(Default)]
+pub struct Effects(Vec<(Effect, u16)>);
+
+impl Effects {
+    /// Put an effect on for `duration` ticks, one already on lasts the longest of both.
+    pub fn add(&mut self, effect: Effect, duration: u16) {
+        match self.0.iter_mut().find(|(on, _)| *on == effect) {
+            Some((_, left)) => *left = (*left).max(duration),
+            None => self.0.push((effect, duration)),
+        }
+    }
+
+    pub fn remove(&mut self, effect: Effect) {
+        self.0.retain(|(on, _)| *on != effect);
+    }
+
+    /// The first effect on taking life away.
+    pub fn harmful(&self) -> Option<Effect> {
+        self.0.iter().map(|&(on, _)| on).find(|on| on.pulse().is_some_and(|(_, change)| change < 0))
+    }
+
+    /// Slowed down beings skip every other tick.
+    pub fn holds_back(&self) -> bool {
+        self.0.iter().any(|&(on, left)| on == Effect::Slow && left % 2 == 1)
+    }
+
+    /// Count the effects down and apply their pulses to `life`, returns what is left of it.
+    pub fn on_tick(&mut self, life: u8, max_life: u8) -> u8 {
+        let mut life = life as i16;
+        for (effect, left) in &mut self.0 {
+            *left = left.saturating_sub(1);
+            if let Some((period, change)) = effect.pulse() {
+                if *left % period == 0 {
+                    life += change;
+                }
+            }
+        }
+        self.0.retain(|(_, left)| *left > 0);
+        life.clamp(0, max_life as i16) as u8
+    }
+
+    /// Effect names with the seconds they still last.
+    pub fn to_spans(&self) -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        for (effect, left) in &self.0 {
+            let seconds = left.div_ceil(TICKS_PER_SECOND);
+            spans.push(Span::styled(format!("{} {}s ", effect.name(), seconds), Style::default().fg(effect.color())));
+        }
+        spans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn effects_on_again_last_the_longest() {
+        let mut effects = Effects::default();
+        effects.add(Effect::Poison, 40);
+        effects.add(Effect::Poison, 10);
+        assert_eq!(effects.0, vec![(Effect::Poison, 40)]);
+        effects.add(Effect::Poison, 60);
+        assert_eq!(effects.0, vec![(Effect::Poison, 60)]);
+    }
+
+    #[test]
+    fn poison_pulses_until_it_wears_off() {
+        let mut effects = Effects::default();
+        effects.add(Effect::Poison, 40);
+        let mut life = 10;
+        for _ in 0..40 {
+            life = effects.on_tick(life, 10);
+        }
+        assert_eq!(life, 6);
+        assert_eq!(effects.harmful(), None);
+        assert_eq!(effects.on_tick(life, 10), 6);
+    }
+
+    #[test]
+    fn life_stays_within_bounds() {
+        let mut effects = Effects::default();
+        effects.add(Effect::Burning, 100);
+        effects.add(Effect::Regeneration, 100);
+        assert_eq!(effects.harmful(), Some(Effect::Burning));
+        let mut life = 1;
+        for _ in 0..10 {
+            life = effects.on_tick(life, 10);
+        }
+        assert_eq!(life, 0);
+
+        let mut effects = Effects::default();
+        effects.add(Effect::Regeneration, 100);
+        assert_eq!(effects.harmful(), None);
+        assert_eq!(effects.on_tick(10, 10), 10);
+    }
+
+    #[test]
+    fn zero_duration_effects_end_right_away() {
+        let mut effects = Effects::default();
+        effects.add(Effect::Slow, 0);
+        assert_eq!(effects.on_tick(5, 10), 5);
+        assert!(effects.0.is_empty());
+    }
+
+    #[test]
+    fn slowness_skips_every_other_tick() {
+        let mut effects = Effects::default();
+        effects.add(Effect::Slow, 4);
+        let mut skipped = 0;
+        for _ in 0..4 {
+            effects.on_tick(5, 10);
+            if effects.holds_back() { skipped += 1 }
+        }
+        assert_eq!(skipped, 2);
+    }
+}
@@ -0,0 +1,168 @@
+use std::cell::Cell;
+use rand::Rng;
+use crate::{game::Game, pathfinding::PathCache};
+
+use super::{player::Player, Body};
+
+/// What a creature is busy doing.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum State {
+    Idle,
+    Wander,
+    Chase,
+    Flee,
+    Attack,
+    ReturnHome,
+    Sleep,
+}
+
+/// How a creature notices the player.
+pub struct Perception {
+    /// The player is noticed closer than this many tiles on both axes.
+    pub radius: i64,
+    /// The player is only noticed when no block stands in between.
+    pub line_of_sight: bool,
+}
+
+impl Perception {
+    pub fn notices(&self, game: &Game, from: (i64, i64), to: (i64, i64), radius: i64) -> bool {
+        (from.0 - to.0).abs() < radius
+            && (from.1 - to.1).abs() < radius
+            && (!self.line_of_sight || in_sight(game, from, to))
+    }
+}
+
+/// No block stands on the straight line between the two tiles.
+fn in_sight(game: &Game, from: (i64, i64), to: (i64, i64)) -> bool {
+    let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs());
+    (1..steps).all(|step| {
+        let x = from.0 + ((to.0 - from.0) as f64 * step as f64 / steps as f64).round() as i64;
+        let y = from.1 + ((to.1 - from.1) as f64 * step as f64 / steps as f64).round() as i64;
+        game.get_block(x, y).is_none()
+    })
+}
+
+/// The behaviours a creature is made of.
+pub struct Behaviour {
+    pub perception: Perception,
+    /// Hurts the player when next to it.
+    pub attacks: bool,
+    /// Follows the player once noticed.
+    pub chases: bool,
+    /// Ticks spent running away after being hurt, 0 to stand its ground.
+    pub flee_time: u16,
+    /// Tiles it strays from where it was born before heading back, 0 to roam freely.
+    pub home_radius: i64,
+    /// Ticks spent idle before falling asleep, 0 for creatures wandering around instead.
+    pub sleep_after: u16,
+}
+
+/// The state of mind of a creature, it goes from one state to another
+/// following its behaviour and what happens around.
+pub struct Brain {
+    state: Cell<State>,
+    /// Ticks left in the current state, for the states that only last a while.
+    timer: Cell<u16>,
+    home: (i64, i64),
+    path: PathCache,
+}
+
+impl Brain {
+    pub fn new(home: (i64, i64)) -> Self {
+        Self {
+            state: Cell::new(State::Wander),
+            timer: Cell::new(0),
+            home,
+            path: PathCache::default(),
+        }
+    }
+
+    pub fn state(&self) -> State {
+        self.state.get()
+    }
+
+    fn set_state(&self, state: State, timer: u16) {
+        if state != self.state.get() {
+            self.state.set(state);
+            self.timer.set(timer);
+            self.path.clear();
+        }
+    }
+
+    pub fn on_tick(&self) {
+        self.timer.set(self.timer.get().saturating_sub(1));
+    }
+
+    /// Getting hurt wakes a creature up and scares the fearful ones.
+    pub fn on_hurt(&self, behaviour: &Behaviour) {
+        if behaviour.flee_time > 0 {
+            self.state.set(State::Flee);
+            self.timer.set(behaviour.flee_time);
+        } else if self.state.get() == State::Sleep {
+            // stay up long enough to face whatever did it
+            self.set_state(State::Idle, behaviour.sleep_after);
+        }
+    }
+
+    /// Pick the state to be in from what the creature perceives.
+    pub fn think(&self, behaviour: &Behaviour, body: &Body, player: &Player, game: &Game) -> State {
+        let position = (body.x, body.y);
+        let target = (player.x(), player.y());
+        let radius = behaviour.perception.radius;
+        let state = self.state.get();
+        let away = (position.0 - self.home.0).abs().max((position.1 - self.home.1).abs());
+        let next = if state == State::Flee && self.timer.get() > 0 {
+            State::Flee
+        } else if behaviour.attacks && (position.0 - target.0).abs() + (position.1 - target.1).abs() == 1 {
+            State::Attack
+        } else if state == State::Sleep && !behaviour.perception.notices(game, position, target, radius / 2) {
+            // sleepers only notice the player once it is very close
+            State::Sleep
+        } else if behaviour.chases && behaviour.perception.notices(game, position, target, radius) {
+            State::Chase
+        } else if behaviour.home_radius > 0 && (away > behaviour.home_radius || state == State::ReturnHome && away > 0) {
+            State::ReturnHome
+        } else if behaviour.sleep_after > 0 {
+            if state == State::Idle && self.timer.get() == 0 { State::Sleep } else { State::Idle }
+        } else {
+            State::Wander
+        };
+        self.set_state(next, behaviour.sleep_after);
+        next
+    }
+
+    /// Tile to step on in the current state, `None` to stay still.
+    pub fn next_tile(&self, body: &Body, player: &Player, game: &Game) -> Option<(i64, i64)> {
+        let position = (body.x, body.y);
+        match self.state.get() {
+            State::Idle | State::Sleep | State::Attack => None,
+            State::Wander => Some(wander(position)),
+            State::Flee => Some(away_from(position, (player.x(), player.y()))),
+            // give up on unreachable goals and walk around instead
+            State::Chase => self.path.next_step(game, position, (player.x(), player.y()))
+                .or_else(|| Some(wander(position))),
+            State::ReturnHome => self.path.next_step(game, position, self.home)
+                .or_else(|| Some(wander(position))),
+        }
+    }
+}
+
+/// Random tile next to the given one.
+fn wander((x, y): (i64, i64)) -> (i64, i64) {
+    match rand::thread_rng().gen_range(0..=3) {
+        0 => (x + 1, y),
+        1 => (x - 1, y),
+        2 => (x, y + 1),
+        _ => (x, y - 1),
+    }
+}
+
+/// Next tile away from the threat, along the axis it is furthest on.
+fn away_from((mut x, mut y): (i64, i64), threat: (i64, i64)) -> (i64, i64) {
+    if (x - threat.0).abs() > (y - threat.1).abs() {
+        x += if x > threat.0 { 1 } else { -1 };
+    } else {
+        y += if y > threat.1 { 1 } else { -1 };
+    }
+    (x, y)
+}
@@ -2,37 +2,39 @@ use tui::{
     style::{Color, Style},
     text::Span,
 };
-use crate::{entities::{Direction, Entity}, game::Game};
+use crate::{entities::{Body, Direction, Entity}, game::Game, items::{ItemKind, arrow::Arrow as ArrowItem}};
 
-use super::{player::Player, Action};
+use super::{player::Player, itemdrop::ItemDrop, projectile::{Impact, Projectile}, Action};
 
 pub struct Arrow {
-    x: i64,
-    y: i64,
-    looking: Direction,
-    life: u8,
-    damage: u8,
+    body: Body,
+    projectile: Projectile,
 }
 
-impl<'a> Arrow {
+impl Arrow {
     pub fn new(x: i64, y: i64, direction: Direction, damage: u8) -> Self {
         Self {
-            x,
-            y,
-            looking: direction,
-            life: 50,
-            damage,
+            body: Body::new(x, y, direction, 1),
+            projectile: Projectile::new(2, 50, 0, damage, Arrow::on_impact),
+        }
+    }
+
+    /// Arrows stick into wood where they can be picked up again, and break on anything else.
+    fn on_impact(impact: Impact, (x, y): (i64, i64)) -> Action {
+        match impact {
+            Impact::Wood => Action::Spawn(vec![Box::new(ItemDrop::new(x, y, ItemKind::Arrow(ArrowItem::new(1))))]),
+            Impact::Stone | Impact::Water => Action::Nothing,
         }
     }
 }
 
-impl<'a> Entity<'a> for Arrow {
-    fn name<'b>(&self) -> &'b str {
-        "swing"
+impl Entity for Arrow {
+    fn name(&self) -> &'static str {
+        "arrow"
     }
 
-    fn shape(&self) -> Span<'a> {
-        match self.looking {
+    fn shape(&self) -> Span<'static> {
+        match self.body.looking {
             Direction::Up => Span::styled("|", Style::default().fg(Color::LightYellow)),
             Direction::Down => Span::styled("|", Style::default().fg(Color::LightYellow)),
             Direction::Left => Span::styled("-", Style::default().fg(Color::LightYellow)),
@@ -40,55 +42,31 @@ impl<'a> Entity<'a> for Arrow {
         }
     }
 
-    fn go(&mut self, x: i64, y: i64) {
-        self.x = x;
-        self.y = y;
-    }
-
     fn on_tick(&mut self) {
-        match self.looking {
-            Direction::Up => self.y += 1,
-            Direction::Down => self.y -= 1,
-            Direction::Left => self.x -= 1,
-            Direction::Right => self.x += 1,
-        }
-        if !self.is_dead() {
-            self.life -= 1;
+        if self.projectile.is_spent() {
+            self.body.life = 0;
         }
     }
 
     fn on_action(&self, _player: &mut Player, game: &Game) -> Action {
-        if let Some(entity_id) = game.get_entity_id(self.x, self.y) {
-            Action::Attack(entity_id, self.damage)
-        } else {
-            Action::Nothing
-        }
+        self.projectile.fly(&self.body, game)
     }
 
-    fn is_dead(&self) -> bool {
-        self.life == 0
+    fn body(&self) -> &Body {
+        &self.body
     }
 
-    fn looking(&mut self) -> Direction {
-        self.looking.to_owned()
+    fn body_mut(&mut self) -> &mut Body {
+        &mut self.body
     }
 
-    fn x(&self) -> i64 {
-        self.x
-    }
-
-    fn y(&self) -> i64 {
-        self.y
-    }
-
-    fn heal(&mut self, _amount: u8) {}
     fn hurt(&mut self, _amount: u8) {}
 
-    fn is_harmful(&self) -> bool {
-        true
-    }
-    
     fn damage(&self) -> u8 {
-        self.damage
+        self.projectile.damage()
+    }
+
+    fn boost(&mut self, bonus: u8) {
+        self.projectile.boost(bonus);
     }
 }
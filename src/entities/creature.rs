@@ -0,0 +1,169 @@
+use tui::{
+    style::{Color, Style},
+    text::Span,
+};
+use crate::{game::Game, effects::Effect};
+
+use super::{ai::{Behaviour, Brain, State}, player::Player, Action, Body, Direction, Entity, EntityKind, IMMUNITY, KNOCKBACK};
+
+/// Ticks a creature waits between two steps.
+const STEP_DELAY: u8 = 10;
+
+/// What sets a kind of creature apart from the others.
+pub struct Species {
+    pub name: &'static str,
+    /// The creature alternates between these two letters.
+    pub frames: [&'static str; 2],
+    pub color: Color,
+    /// Color while immune after a hit.
+    pub hurt_color: Color,
+    pub life: u8,
+    pub damage: u8,
+    /// Effect its attacks leave on the player and for how many ticks.
+    pub inflicts: Option<(Effect, u16)>,
+    /// Experience given to the player for killing one.
+    pub xp: u32,
+    pub behaviour: Behaviour,
+}
+
+impl Species {
+    pub fn spawn(&'static self, x: i64, y: i64) -> EntityKind {
+        Box::new(Creature::new(self, x, y))
+    }
+}
+
+/// A creature living in the world, acting as its species behaves.
+pub struct Creature {
+    species: &'static Species,
+    body: Body,
+    frame: u8,
+    until_next_step: u8,
+    brain: Brain,
+}
+
+impl Creature {
+    pub fn new(species: &'static Species, x: i64, y: i64) -> Self {
+        Self {
+            species,
+            body: Body::new(x, y, Direction::Up, species.life),
+            frame: 0,
+            until_next_step: STEP_DELAY,
+            brain: Brain::new((x, y)),
+        }
+    }
+
+    /// Waiting for its next step.
+    pub fn is_resting(&self) -> bool {
+        self.until_next_step > 0
+    }
+
+    /// Step to (x, y) once rested, rest twice as fast in a hurry.
+    pub fn walk(&mut self, x: i64, y: i64, hurry: bool) {
+        if self.until_next_step > 0 {
+            self.until_next_step -= 1;
+            if hurry && self.until_next_step > 0 {
+                self.until_next_step -= 1;
+            }
+        } else {
+            if (x, y) != (self.body.x, self.body.y) {
+                self.body.looking = Direction::towards((self.body.x, self.body.y), (x, y));
+            }
+            self.body.x = x;
+            self.body.y = y;
+            self.until_next_step = STEP_DELAY;
+        }
+    }
+
+    /// Move to (x, y) if there is nothing there already.
+    pub fn step_to(&self, (x, y): (i64, i64), player: &Player, game: &Game) -> Action {
+        if game.is_available(x, y) && (player.x() != x || player.y() != y) {
+            Action::Move(x, y)
+        } else {
+            Action::Nothing
+        }
+    }
+}
+
+impl Entity for Creature {
+    fn name(&self) -> &'static str {
+        self.species.name
+    }
+
+    /// Tougher ones than usual, such as bosses, are worth more.
+    fn xp(&self) -> u32 {
+        self.species.xp * self.body.max_life as u32 / self.species.life as u32
+    }
+
+    fn shape(&self) -> Span<'static> {
+        let color = if self.body.immunity == 0 {
+            self.species.color
+        } else {
+            self.species.hurt_color
+        };
+        // sleepers lie still
+        let frame = if self.brain.state() == State::Sleep { 1 } else { self.frame as usize / 10 };
+        Span::styled(self.species.frames[frame], Style::default().fg(color))
+    }
+
+    fn body(&self) -> &Body {
+        &self.body
+    }
+
+    fn body_mut(&mut self) -> &mut Body {
+        &mut self.body
+    }
+
+    fn go(&mut self, x: i64, y: i64) {
+        if !self.body.effects.holds_back() {
+            self.walk(x, y, self.brain.state() == State::Flee)
+        }
+    }
+
+    fn on_tick(&mut self) {
+        self.frame = (self.frame + 1) % 20;
+        if self.body.immunity > 0 { self.body.immunity -= 1 }
+        self.body.suffer_effects();
+        self.brain.on_tick();
+    }
+
+    fn on_action(&self, player: &mut Player, game: &Game) -> Action {
+        let state = self.brain.think(&self.species.behaviour, &self.body, player, game);
+        if state == State::Attack {
+            if player.hurt(self.species.damage, self.species.name) {
+                let direction = Direction::towards(self.position(), (player.x(), player.y()));
+                player.knock_back(direction, KNOCKBACK, game);
+                if let Some((effect, duration)) = self.species.inflicts {
+                    player.afflict(effect, duration);
+                }
+            }
+            return Action::Nothing;
+        }
+
+        // try to move
+        if self.is_resting() {
+            return Action::Move(self.body.x, self.body.y);
+        }
+        match self.brain.next_tile(&self.body, player, game) {
+            Some(tile) => self.step_to(tile, player, game),
+            None => Action::Nothing,
+        }
+    }
+
+    fn hurt(&mut self, amount: u8) {
+        if self.body.immunity == 0 {
+            self.body.life = self.body.life.saturating_sub(amount);
+            self.body.immunity = IMMUNITY;
+            if !self.is_dead() {
+                self.brain.on_hurt(&self.species.behaviour);
+            }
+        }
+    }
+
+    fn damage(&self) -> u8 {
+        self.species.damage
+    }
+
+    fn is_hostile(&self) -> bool {
+        self.species.behaviour.attacks
+    }
+}
@@ -0,0 +1,69 @@
+use tui::text::Span;
+use crate::{entities::{Body, Direction, Entity}, game::Game, items::ItemKind};
+
+use super::{player::Player, Action};
+
+/// Ticks an item stays on the ground before vanishing, a minute at 20 ticks a second.
+const DESPAWN_TIME: u16 = 1200;
+
+/// An item lying on the ground.
+pub struct ItemDrop {
+    body: Body,
+    item: Option<ItemKind>,
+    until_despawn: u16,
+}
+
+impl ItemDrop {
+    pub fn new(x: i64, y: i64, item: ItemKind) -> Self {
+        Self {
+            body: Body::new(x, y, Direction::Up, 1),
+            item: Some(item),
+            until_despawn: DESPAWN_TIME,
+        }
+    }
+}
+
+impl Entity for ItemDrop {
+    fn name(&self) -> &'static str {
+        "item"
+    }
+
+    fn shape(&self) -> Span<'static> {
+        self.item.as_ref().map_or(Span::raw(" "), |item| item.shape())
+    }
+
+    fn body(&self) -> &Body {
+        &self.body
+    }
+
+    fn body_mut(&mut self) -> &mut Body {
+        &mut self.body
+    }
+
+    fn on_tick(&mut self) {
+        if self.until_despawn > 0 {
+            self.until_despawn -= 1;
+        } else {
+            self.body.life = 0;
+        }
+    }
+
+    fn on_action(&self, _player: &mut Player, _game: &Game) -> Action {
+        Action::Nothing
+    }
+
+    fn take_item(&mut self) -> Option<ItemKind> {
+        self.body.life = 0;
+        self.item.take()
+    }
+
+    fn hurt(&mut self, _amount: u8) {}
+
+    fn is_harmful(&self) -> bool {
+        false
+    }
+
+    fn is_solid(&self) -> bool {
+        false
+    }
+}
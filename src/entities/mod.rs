@@ -1,8 +1,10 @@
 use tui::{widgets::canvas::Context, text::Span};
-use crate::game::Game;
-use self::{onyxstone::OnyxStone, fire::Fire, swing::Swing, player::Player, snake::Snake, ovis::Ovis, crawler::Crawler, golem::Golem, scorpy::Scorpy, arrow::Arrow};
+use crate::{game::Game, items::ItemKind, effects::{Effect, Effects}};
+use self::{player::Player, snake::SNAKE, ovis::OVIS, scorpy::SCORPY, golem::GOLEM, crawler::CRAWLER};
 
 pub mod player;
+pub mod ai;
+pub mod creature;
 pub mod onyxstone;
 pub mod fire;
 pub mod swing;
@@ -12,8 +14,19 @@ pub mod scorpy;
 pub mod golem;
 pub mod crawler;
 pub mod arrow;
+pub mod projectile;
+pub mod itemdrop;
 
-#[derive(Clone)]
+/// Ticks during which an entity can't be hurt again after a hit.
+const IMMUNITY: u8 = 10;
+
+/// Tiles a hit that goes through pushes its target back.
+pub const KNOCKBACK: i64 = 2;
+
+/// Damage is multiplied by this when hitting from behind.
+pub const BACKSTAB: u8 = 2;
+
+#[derive(Clone, PartialEq)]
 pub enum Direction {
     Up,
     Down,
@@ -21,211 +34,221 @@ pub enum Direction {
     Right,
 }
 
-pub enum EntityKind {
-    OnyxStone(OnyxStone),
-    Fire(Fire),
-    Swing(Swing),
-    Snake(Snake),
-    Ovis(Ovis),
-    Scorpy(Scorpy),
-    Golem(Golem),
-    Crawler(Crawler),
-    Arrow(Arrow),
+impl Direction {
+    /// Tile offset of a step this way.
+    pub fn offset(&self) -> (i64, i64) {
+        match self {
+            Direction::Up => (0, 1),
+            Direction::Down => (0, -1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+
+    /// Way from one tile to another, along the axis they are furthest apart on.
+    pub fn towards(from: (i64, i64), to: (i64, i64)) -> Self {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        if dx.abs() > dy.abs() {
+            if dx > 0 { Direction::Right } else { Direction::Left }
+        } else if dy >= 0 {
+            Direction::Up
+        } else {
+            Direction::Down
+        }
+    }
 }
 
+pub type EntityKind = Box<dyn Entity>;
+
+/// Identifies an entity for its whole life, unlike its place in the entity list
+/// which changes as others spawn and die. Ids are never reused.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct EntityId(pub u64);
+
 pub enum Action {
     Move(i64, i64),
-    Attack(usize, u8),
+    /// Hit an entity, the blow travels in the given direction.
+    Attack(EntityId, u8, Direction),
     Spawn(Vec<EntityKind>),
     Nothing
 }
 
-impl EntityKind {
-    pub fn on_tick(&mut self) {
-        match self {
-            EntityKind::OnyxStone(e) => e.on_tick(),
-            EntityKind::Fire(e) => e.on_tick(),
-            EntityKind::Swing(e) => e.on_tick(),
-            EntityKind::Snake(e) => e.on_tick(),
-            EntityKind::Ovis(e) => e.on_tick(),
-            EntityKind::Scorpy(e) => e.on_tick(),
-            EntityKind::Golem(e) => e.on_tick(),
-            EntityKind::Crawler(e) => e.on_tick(),
-            EntityKind::Arrow(e) => e.on_tick(),
+type Spawner = fn(i64, i64) -> EntityKind;
+
+/// Creatures that can be spawned from their name, short lived entities such as fire or arrows are not listed.
+const CREATURES: [(&str, Spawner); 5] = [
+    ("snake", |x, y| SNAKE.spawn(x, y)),
+    ("ovis", |x, y| OVIS.spawn(x, y)),
+    ("scorpy", |x, y| SCORPY.spawn(x, y)),
+    ("golem", |x, y| GOLEM.spawn(x, y)),
+    ("crawler", |x, y| CRAWLER.spawn(x, y)),
+];
+
+pub fn spawn(name: &str, x: i64, y: i64) -> Option<EntityKind> {
+    CREATURES.iter().find(|(key, _)| *key == name).map(|(_, new)| new(x, y))
+}
+
+pub fn is_creature(name: &str) -> bool {
+    CREATURES.iter().any(|(key, _)| *key == name)
+}
+
+/// Position, facing and health every entity has.
+pub struct Body {
+    /// Given by the game when the entity enters the world.
+    pub id: EntityId,
+    pub x: i64,
+    pub y: i64,
+    pub looking: Direction,
+    pub life: u8,
+    pub max_life: u8,
+    pub immunity: u8,
+    pub effects: Effects,
+}
+
+impl Body {
+    pub fn new(x: i64, y: i64, looking: Direction, life: u8) -> Self {
+        Self {
+            id: EntityId::default(),
+            x,
+            y,
+            looking,
+            life,
+            max_life: life,
+            immunity: 0,
+            effects: Effects::default(),
         }
     }
 
-    pub fn go(&mut self, x: i64, y: i64) {
-        match self {
-            EntityKind::OnyxStone(e) => e.go(x, y),
-            EntityKind::Fire(e) => e.go(x, y),
-            EntityKind::Swing(e) => e.go(x, y),
-            EntityKind::Snake(e) => e.go(x, y),
-            EntityKind::Ovis(e) => e.go(x, y),
-            EntityKind::Scorpy(e) => e.go(x, y),
-            EntityKind::Golem(e) => e.go(x, y),
-            EntityKind::Crawler(e) => e.go(x, y),
-            EntityKind::Arrow(e) => e.go(x, y),
-        }
+    /// Wear the effects down, they may hurt or heal on the way.
+    pub fn suffer_effects(&mut self) {
+        self.life = self.effects.on_tick(self.life, self.max_life);
     }
+}
 
-    pub fn hurt(&mut self, amount: u8) {
-        match self {
-            EntityKind::OnyxStone(e) => e.hurt(amount),
-            EntityKind::Fire(e) => e.hurt(amount),
-            EntityKind::Swing(e) => e.hurt(amount),
-            EntityKind::Snake(e) => e.hurt(amount),
-            EntityKind::Ovis(e) => e.hurt(amount),
-            EntityKind::Scorpy(e) => e.hurt(amount),
-            EntityKind::Golem(e) => e.hurt(amount),
-            EntityKind::Crawler(e) => e.hurt(amount),
-            EntityKind::Arrow(e) => e.hurt(amount),
-        }
+pub trait Entity {
+    fn name(&self) -> &'static str;
+    fn shape(&self) -> Span<'static>;
+    fn body(&self) -> &Body;
+    fn body_mut(&mut self) -> &mut Body;
+    fn on_action(&self, player: &mut Player, game: &Game) -> Action;
+
+    fn id(&self) -> EntityId {
+        self.body().id
     }
 
-    pub fn on_action(&self, player: &mut Player, game: &Game) -> Action {
-        match self {
-            EntityKind::OnyxStone(e) => e.on_action(player, game),
-            EntityKind::Fire(e) => e.on_action(player, game),
-            EntityKind::Swing(e) => e.on_action(player, game),
-            EntityKind::Snake(e) => e.on_action(player, game),
-            EntityKind::Ovis(e) => e.on_action(player, game),
-            EntityKind::Scorpy(e) => e.on_action(player, game),
-            EntityKind::Golem(e) => e.on_action(player, game),
-            EntityKind::Crawler(e) => e.on_action(player, game),
-            EntityKind::Arrow(e) => e.on_action(player, game),
-        }
+    fn x(&self) -> i64 {
+        self.body().x
     }
 
-    pub fn draw(&self, ctx: &mut Context) {
-        match self {
-            EntityKind::OnyxStone(e) => ctx.print(e.x() as f64, e.y() as f64, e.shape()),
-            EntityKind::Fire(e) => ctx.print(e.x() as f64, e.y() as f64, e.shape()),
-            EntityKind::Swing(e) => ctx.print(e.x() as f64, e.y() as f64, e.shape()),
-            EntityKind::Snake(e) => ctx.print(e.x() as f64, e.y() as f64, e.shape()),
-            EntityKind::Ovis(e) => ctx.print(e.x() as f64, e.y() as f64, e.shape()),
-            EntityKind::Scorpy(e) => ctx.print(e.x() as f64, e.y() as f64, e.shape()),
-            EntityKind::Golem(e) => ctx.print(e.x() as f64, e.y() as f64, e.shape()),
-            EntityKind::Crawler(e) => ctx.print(e.x() as f64, e.y() as f64, e.shape()),
-            EntityKind::Arrow(e) => ctx.print(e.x() as f64, e.y() as f64, e.shape()),
-        };
-    }
-
-    pub fn looking_at(&mut self) -> (i64, i64, Direction) {
-        match self {
-            EntityKind::OnyxStone(e) => e.looking_at(),
-            EntityKind::Fire(e) => e.looking_at(),
-            EntityKind::Swing(e) => e.looking_at(),
-            EntityKind::Snake(e) => e.looking_at(),
-            EntityKind::Ovis(e) => e.looking_at(),
-            EntityKind::Scorpy(e) => e.looking_at(),
-            EntityKind::Golem(e) => e.looking_at(),
-            EntityKind::Crawler(e) => e.looking_at(),
-            EntityKind::Arrow(e) => e.looking_at(),
-        }
+    fn y(&self) -> i64 {
+        self.body().y
     }
-    
-    pub fn looking(&mut self) -> Direction {
-        match self {
-            EntityKind::OnyxStone(e) => e.looking(),
-            EntityKind::Fire(e) => e.looking(),
-            EntityKind::Swing(e) => e.looking(),
-            EntityKind::Snake(e) => e.looking(),
-            EntityKind::Ovis(e) => e.looking(),
-            EntityKind::Scorpy(e) => e.looking(),
-            EntityKind::Golem(e) => e.looking(),
-            EntityKind::Crawler(e) => e.looking(),
-            EntityKind::Arrow(e) => e.looking(),
-        }
+
+    fn position(&self) -> (i64, i64) {
+        (self.x(), self.y())
     }
 
-    pub fn is_dead(&self) -> bool {
-        match self {
-            EntityKind::OnyxStone(e) => e.is_dead(),
-            EntityKind::Fire(e) => e.is_dead(),
-            EntityKind::Swing(e) => e.is_dead(),
-            EntityKind::Snake(e) => e.is_dead(),
-            EntityKind::Ovis(e) => e.is_dead(),
-            EntityKind::Scorpy(e) => e.is_dead(),
-            EntityKind::Golem(e) => e.is_dead(),
-            EntityKind::Crawler(e) => e.is_dead(),
-            EntityKind::Arrow(e) => e.is_dead(),
-        }
+    fn collide(&self, x: i64, y: i64) -> bool {
+        self.position() == (x, y)
     }
 
-    pub fn collide(&self, x: i64, y: i64) -> bool {
-        match self {
-            EntityKind::OnyxStone(e) => (x, y) == (e.x(), e.y()),
-            EntityKind::Fire(e) => (x, y) == (e.x(), e.y()),
-            EntityKind::Swing(e) => (x, y) == (e.x(), e.y()),
-            EntityKind::Snake(e) => (x, y) == (e.x(), e.y()),
-            EntityKind::Ovis(e) => (x, y) == (e.x(), e.y()),
-            EntityKind::Scorpy(e) => (x, y) == (e.x(), e.y()),
-            EntityKind::Golem(e) => (x, y) == (e.x(), e.y()),
-            EntityKind::Crawler(e) => (x, y) == (e.x(), e.y()),
-            EntityKind::Arrow(e) => (x, y) == (e.x(), e.y()),
-        }
+    fn go(&mut self, x: i64, y: i64) {
+        let body = self.body_mut();
+        body.x = x;
+        body.y = y;
     }
 
-    pub fn is_harmful(&self) -> bool {
-        match self {
-            EntityKind::OnyxStone(e) => e.is_harmful(),
-            EntityKind::Fire(e) => e.is_harmful(),
-            EntityKind::Swing(e) => e.is_harmful(),
-            EntityKind::Snake(e) => e.is_harmful(),
-            EntityKind::Ovis(e) => e.is_harmful(),
-            EntityKind::Scorpy(e) => e.is_harmful(),
-            EntityKind::Golem(e) => e.is_harmful(),
-            EntityKind::Crawler(e) => e.is_harmful(),
-            EntityKind::Arrow(e) => e.is_harmful(),
-        }
+    fn on_tick(&mut self) {
+        let body = self.body_mut();
+        if body.immunity > 0 { body.immunity -= 1 }
+        body.suffer_effects();
     }
 
-    pub fn damage(&self) -> u8 {
-        match self {
-            EntityKind::OnyxStone(e) => e.damage(),
-            EntityKind::Fire(e) => e.damage(),
-            EntityKind::Swing(e) => e.damage(),
-            EntityKind::Snake(e) => e.damage(),
-            EntityKind::Ovis(e) => e.damage(),
-            EntityKind::Scorpy(e) => e.damage(),
-            EntityKind::Golem(e) => e.damage(),
-            EntityKind::Crawler(e) => e.damage(),
-            EntityKind::Arrow(e) => e.damage(),
-        }
+    fn is_dead(&self) -> bool {
+        self.body().life == 0
     }
 
-    pub fn name<'a>(&self) -> &'a str {
-        match self {
-            EntityKind::OnyxStone(e) => e.name(),
-            EntityKind::Fire(e) => e.name(),
-            EntityKind::Swing(e) => e.name(),
-            EntityKind::Snake(e) => e.name(),
-            EntityKind::Ovis(e) => e.name(),
-            EntityKind::Scorpy(e) => e.name(),
-            EntityKind::Golem(e) => e.name(),
-            EntityKind::Crawler(e) => e.name(),
-            EntityKind::Arrow(e) => e.name(),
+    fn hurt(&mut self, amount: u8) {
+        let body = self.body_mut();
+        if body.immunity == 0 {
+            body.life = body.life.saturating_sub(amount);
+            body.immunity = IMMUNITY;
         }
     }
-}
 
-pub trait Entity<'a> {
-    fn x(&self) -> i64;
-    fn y(&self) -> i64;
-    fn go(&mut self, x: i64, y: i64);
-    fn shape(&self) -> Span<'a>;
-    fn on_tick(&mut self);
-    fn on_action(&self, player: &mut Player, game: &Game) -> Action;
-    fn is_dead(&self) -> bool;
-    fn hurt(&mut self, amount: u8);
-    fn heal(&mut self, amount: u8);
-    fn looking(&mut self) -> Direction;
-    fn is_harmful(&self) -> bool;
-    fn damage(&self) -> u8;
-    fn name<'b>(&self) -> &'b str;
-    
-    fn looking_at(&mut self) -> (i64, i64, Direction) {
+    fn afflict(&mut self, effect: Effect, duration: u16) {
+        self.body_mut().effects.add(effect, duration);
+    }
+
+    fn cure(&mut self, effect: Effect) {
+        self.body_mut().effects.remove(effect);
+    }
+
+    /// Effect caught by whoever stands on the same tile.
+    fn afflicts(&self) -> Option<(Effect, u16)> {
+        None
+    }
+
+    /// Experience given to the player for killing it.
+    fn xp(&self) -> u32 {
+        0
+    }
+
+    /// Hit harder by `bonus`, for projectiles shot by a skilled hand.
+    fn boost(&mut self, _bonus: u8) {}
+
+    /// Whether it takes up its tile, keeping others from walking onto it.
+    fn is_solid(&self) -> bool {
+        true
+    }
+
+    /// Blows of the tool in the player's hand, which wear it when they land.
+    fn wears_tool(&self) -> bool {
+        false
+    }
+
+    fn heal(&mut self, amount: u8) {
+        let body = self.body_mut();
+        body.life = body.life.saturating_add(amount).min(body.max_life);
+    }
+
+    fn life(&self) -> u8 {
+        self.body().life
+    }
+
+    fn set_life(&mut self, life: u8) {
+        let body = self.body_mut();
+        body.life = life;
+        body.max_life = body.max_life.max(life);
+    }
+
+    fn looking(&self) -> Direction {
+        self.body().looking.to_owned()
+    }
+
+    fn is_harmful(&self) -> bool {
+        true
+    }
+
+    /// Item carried by the entity, taken off it when picked up.
+    fn take_item(&mut self) -> Option<ItemKind> {
+        None
+    }
+
+    /// Attacks whoever comes close.
+    fn is_hostile(&self) -> bool {
+        false
+    }
+
+    fn damage(&self) -> u8 {
+        0
+    }
+
+    fn draw(&self, ctx: &mut Context) {
+        ctx.print(self.x() as f64, self.y() as f64, self.shape())
+    }
+
+    fn looking_at(&self) -> (i64, i64, Direction) {
         match self.looking() {
             Direction::Up => (self.x(), self.y() + 1, Direction::Up),
             Direction::Down => (self.x(), self.y() - 1, Direction::Down),
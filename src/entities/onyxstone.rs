@@ -2,84 +2,55 @@ use tui::{
     style::{Color, Style},
     text::Span,
 };
-use crate::{entities::{Direction, Entity}, game::Game};
+use crate::{entities::{Body, Direction, Entity}, game::Game};
 
-use super::player::Player;
+use super::{player::Player, projectile::Projectile, Action};
 
+/// Heavy enough to go through a few creatures, it shatters on whatever stops it.
 pub struct OnyxStone {
-    x: i64,
-    y: i64,
-    looking: Direction,
-    life: u8,
-    damage: u8,
+    body: Body,
+    projectile: Projectile,
 }
 
-impl<'a> OnyxStone {
+impl OnyxStone {
     pub fn new(x: i64, y: i64, direction: Direction) -> Self {
         Self {
-            x,
-            y,
-            looking: direction,
-            life: 20,
-            damage: 10,
+            body: Body::new(x, y, direction, 1),
+            projectile: Projectile::new(1, 20, 2, 10, |_, _| Action::Nothing),
         }
     }
 }
 
-impl<'a> Entity<'a> for OnyxStone {
-    fn name<'b>(&self) -> &'b str {
+impl Entity for OnyxStone {
+    fn name(&self) -> &'static str {
         "onyx stone"
     }
 
-    fn shape(&self) -> Span<'a> {
+    fn shape(&self) -> Span<'static> {
         Span::styled("*", Style::default().fg(Color::Yellow))
     }
 
     fn on_tick(&mut self) {
-        match self.looking {
-            Direction::Up => self.y += 1,
-            Direction::Down => self.y -= 1,
-            Direction::Left => self.x -= 1,
-            Direction::Right => self.x += 1,
+        if self.projectile.is_spent() {
+            self.body.life = 0;
         }
-        if !self.is_dead() {
-            self.life -= 1;
-        }
-    }
-
-    fn go(&mut self, x: i64, y: i64) {
-        self.x = x;
-        self.y = y;
-    }
-
-    fn on_action(&self, _player: &mut Player, _game: &Game) -> super::Action {
-        super::Action::Nothing
-    }
-
-    fn is_dead(&self) -> bool {
-        self.life == 0
     }
 
-    fn looking(&mut self) -> Direction {
-        self.looking.to_owned()
+    fn on_action(&self, _player: &mut Player, game: &Game) -> Action {
+        self.projectile.fly(&self.body, game)
     }
 
-    fn x(&self) -> i64 {
-        self.x
+    fn body(&self) -> &Body {
+        &self.body
     }
 
-    fn y(&self) -> i64 {
-        self.y
+    fn body_mut(&mut self) -> &mut Body {
+        &mut self.body
     }
 
-    fn heal(&mut self, _amount: u8) {}
     fn hurt(&mut self, _amount: u8) {}
 
-    fn is_harmful(&self) -> bool {
-        true
-    }
-    
     fn damage(&self) -> u8 {
-        self.damage
+        self.projectile.damage()
     }
 }
@@ -4,19 +4,44 @@ use tui::{
     text::Span,
     widgets::canvas::Context,
 };
-use crate::{entities::{Direction, EntityKind}, items::ItemKind, game::Game, inventory::Inventory};
+use crate::{entities::{Direction, EntityKind}, items::{ItemKind, boat::Boat}, blocks::BlockKind, game::Game, inventory::Inventory, chunk::Terrain, effects::{Effect, Effects}, equipment::{Equipment, Slot}, experience::{Experience, Perk, LIFE_PER_LEVEL, MARKSMAN_DAMAGE, BRAWLER_DAMAGE}};
+
+/// Hunger of a full stomach.
+pub const MAX_HUNGER: u8 = 100;
+/// Appetite built up before a point of hunger is lost, every tick builds one, even standing still.
+const APPETITE: u16 = 200;
+/// Extra appetite built up by a tick of sprinting and by a blow dealt.
+const SPRINT_APPETITE: u16 = 2;
+const FIGHT_APPETITE: u16 = 20;
+/// Ticks between two points of life lost when starving or gained when well fed.
+const DIGESTION: u16 = 40;
+/// Hunger from which the stomach is full enough to heal.
+const WELL_FED: u8 = 90;
 
 pub struct Player {
     pub x: i64,
     pub y: i64,
     looking: Direction,
     moving: bool,
+    afloat: bool,
     style: Style,
     inventory: Inventory,
     using: usize,
     life: u8,
     max_life: u8,
     immunity: u8,
+    effects: Effects,
+    equipment: Equipment,
+    sprinting: bool,
+    hunger: u8,
+    appetite: u16,
+    digestion: u16,
+    /// What hurt the player last, told when they die.
+    cause: String,
+    /// Ticks lived since the last time the player came to life.
+    age: u32,
+    kills: u32,
+    experience: Experience,
 }
 
 impl<'a> Player {
@@ -26,12 +51,23 @@ impl<'a> Player {
             y,
             looking: Direction::Up,
             moving: false,
+            afloat: false,
             style: Style::default().fg(Color::Black),
             inventory: Inventory::new_player(),
             using: 0,
             life: 100,
             max_life: 100,
             immunity: 20,
+            effects: Effects::default(),
+            equipment: Equipment::default(),
+            sprinting: false,
+            hunger: MAX_HUNGER,
+            appetite: 0,
+            digestion: 0,
+            cause: String::new(),
+            age: 0,
+            kills: 0,
+            experience: Experience::default(),
         }
     }
 
@@ -56,7 +92,13 @@ impl<'a> Player {
             Direction::Left => x -= 1,
             Direction::Right => x += 1,
         }
-        if game.is_available(x, y) {
+        for item in game.pick_up_at(x, y) {
+            game.set_message(format!("picked up {} x{}", item.name(), item.quantity()));
+            self.pick_up(item);
+        }
+        // a boat carried anywhere in the inventory keeps the player afloat
+        let sailing = self.inventory.find(&ItemKind::Boat(Boat::new(1))).is_some();
+        if game.take_stairs(x, y) || game.is_available(x, y) || sailing && game.is_sailable(x, y) {
             self.x = x;
             self.y = y;
         }
@@ -64,6 +106,7 @@ impl<'a> Player {
 
     pub fn on_arrow(&mut self, key: &KeyEvent, direction: Direction) {
         self.look(direction);
+        self.sprinting = key.modifiers.contains(KeyModifiers::SHIFT);
         if key.modifiers.contains(KeyModifiers::CONTROL) {
             self.moving(false);
         } else {
@@ -72,6 +115,14 @@ impl<'a> Player {
     }
 
     pub fn on_space(&mut self, game: &mut Game) -> Option<EntityKind> {
+        if let Some(message) = self.cook(game).or_else(|| self.dig_up(game)).or_else(|| self.eat(self.using)).or_else(|| self.place(game)) {
+            game.set_message(message);
+            return None;
+        }
+        if self.afloat {
+            game.set_message(String::from("you can't use tools while afloat"));
+            return None;
+        }
         let (x, y, _) = self.looking_at();
         let mut message: String;
         message = format!("{} {} test", x , y);
@@ -81,24 +132,178 @@ impl<'a> Player {
                 let item_collected = block.collect();
                 message = format!("collected {} x{}", item_collected.name(), item.quantity());
                 self.inventory.add(item_collected);
+                if self.experience.has(Perk::Miner) && !block.is_destroyed() {
+                    self.inventory.add(block.collect());
+                }
                 if block.is_destroyed() {
+                    let xp = block.xp();
                     game.destroy_block(x, y);
+                    if let Some(level) = self.gain_xp(xp) {
+                        message = format!("{}, {}", message, level);
+                    }
+                }
+                if let Some(broke) = self.wear(false) {
+                    message = format!("{}, {}", message, broke);
                 }
             } else {
-                message = format!("you can't do that");
+                message = String::from("you can't do that");
+            }
+        } else if let Some((id, name)) = game.get_creature_id(x, y).and_then(|id| game.get_entity(id).map(|e| (id, e.name()))) {
+            // creatures are fought with the main hand weapon when there is one
+            let main_hand = self.equipment.get(Slot::MainHand);
+            let mut weapon = main_hand.map_or(item.damage(), |weapon| weapon.damage());
+            let main_hand = main_hand.is_some();
+            if self.experience.has(Perk::Brawler) {
+                weapon = weapon.saturating_add(BRAWLER_DAMAGE);
+            }
+            let struck = game.strike(id, weapon, self.looking.to_owned());
+            self.appetite += FIGHT_APPETITE;
+            message = match struck {
+                Some(damage) if damage > weapon => format!("backstabbed {} for {}", name, damage),
+                Some(damage) => format!("dealt {} to {}", damage, name),
+                None => format!("the {} shrugged it off", name),
+            };
+            if let Some(xp) = game.get_entity(id).filter(|e| e.is_dead()).map(|e| e.xp()) {
+                if let Some(level) = self.add_kill(xp) {
+                    message = format!("{}, {}", message, level);
+                }
+            }
+            if let Some(broke) = struck.and_then(|_| self.wear(main_hand)) {
+                message = format!("{}, {}", message, broke);
+            }
+        } else if matches!(item, ItemKind::Pickaxe(_)) && game.dig_hole(x, y) {
+            message = String::from("you dug a hole down to the caves");
+            if let Some(broke) = self.wear(false) {
+                message = format!("{}, {}", message, broke);
             }
-        } else if let Some(entity_id) = game.get_entity_id(x, y) {
-            let entity = &mut game.mut_entities()[entity_id];
-            entity.hurt(item.damage());
-            message = format!("dealt {} to {}", item.damage(), entity.name());
         } else {
             game.set_message(message);
-            return item.utilize((x, y, self.looking.to_owned()));
+            return self.utilize((x, y, self.looking.to_owned()), game);
         }
         game.set_message(message);
         None
     }
 
+    /// Use the item in hand, spending the ammunition it needs.
+    fn utilize(&mut self, coords: (i64, i64, Direction), game: &mut Game) -> Option<EntityKind> {
+        if let Some(ammunition) = self.inventory.get(self.using).ammunition() {
+            // the offhand is a quiver, drawn from before the inventory
+            if let Some(quiver) = self.equipment.get_mut(Slot::Offhand).filter(|item| item.key() == ammunition.key()) {
+                quiver.change_quantity(-1);
+                if quiver.quantity() == 0 {
+                    self.equipment.take(Slot::Offhand);
+                }
+            } else {
+                let Some(index) = self.inventory.find(&ammunition) else {
+                    game.set_message(format!("you have no {} left", ammunition.name()));
+                    return None;
+                };
+                let item = self.inventory.get(index);
+                item.change_quantity(-1);
+                if item.quantity() == 0 {
+                    self.take_out(index);
+                }
+            }
+            self.appetite += FIGHT_APPETITE;
+        }
+        let mut entity = self.inventory.get(self.using).utilize(coords);
+        if matches!(self.inventory.get(self.using), ItemKind::Bow(_)) && self.experience.has(Perk::Marksman) {
+            if let Some(arrow) = entity.as_mut() {
+                arrow.boost(MARKSMAN_DAMAGE);
+            }
+        }
+        entity
+    }
+
+    /// Wear down the item in hand after one of its swings landed.
+    pub fn wear_in_hand(&mut self) -> Option<String> {
+        self.wear(false)
+    }
+
+    /// Wear down the tool used, the main hand weapon or else the item in hand,
+    /// it is thrown away once broken. Returns what happened when it broke.
+    fn wear(&mut self, main_hand: bool) -> Option<String> {
+        let tool = if main_hand {
+            self.equipment.get_mut(Slot::MainHand)?
+        } else {
+            self.inventory.get(self.using)
+        };
+        if !tool.wear() {
+            return None;
+        }
+        let message = format!("your {} broke", tool.full_name());
+        if main_hand {
+            self.equipment.take(Slot::MainHand);
+        } else {
+            self.take_out(self.using);
+        }
+        Some(message)
+    }
+
+    /// Eat an item of the inventory if it is food. Returns what happened.
+    pub fn eat(&mut self, index: usize) -> Option<String> {
+        let item = self.inventory.get(index);
+        let nourishment = item.nourishment()?;
+        let effect = item.effect();
+        let mut message = format!("you ate {}", item.name());
+        item.change_quantity(-1);
+        if item.quantity() == 0 {
+            self.take_out(index);
+        }
+        self.hunger = (self.hunger + nourishment).min(MAX_HUNGER);
+        if let Some((effect, duration)) = effect {
+            self.effects.add(effect, duration);
+            message = format!("{}, you are {}", message, effect.name());
+        }
+        Some(message)
+    }
+
+    /// Cook the item in hand over the campfire in front, one at a time.
+    fn cook(&mut self, game: &Game) -> Option<String> {
+        let (x, y, _) = self.looking_at();
+        if !matches!(game.get_block(x, y), Some(BlockKind::Campfire(_))) {
+            return None;
+        }
+        let item = self.inventory.get(self.using);
+        let cooked = item.cooked()?;
+        let message = format!("you cooked {} into {}", item.name(), cooked.name());
+        item.change_quantity(-1);
+        if item.quantity() == 0 {
+            self.take_out(self.using);
+        }
+        self.inventory.add(cooked);
+        Some(message)
+    }
+
+    /// Set the item in hand up in front if it becomes a block, such as a campfire.
+    fn place(&mut self, game: &mut Game) -> Option<String> {
+        let (x, y, _) = self.looking_at();
+        let item = self.inventory.get(self.using);
+        if !game.place_block(x, y, item.placed()?) {
+            return None;
+        }
+        let message = format!("you set up a {}", item.name());
+        item.change_quantity(-1);
+        if item.quantity() == 0 {
+            self.take_out(self.using);
+        }
+        Some(message)
+    }
+
+    /// Take back what was buried in the grave in front.
+    fn dig_up(&mut self, game: &mut Game) -> Option<String> {
+        let (x, y, _) = self.looking_at();
+        if !matches!(game.get_block(x, y), Some(BlockKind::Grave(_))) {
+            return None;
+        }
+        let items = game.exhume(x, y);
+        let message = format!("you dug up your grave, {} items recovered", items.len());
+        for item in items {
+            self.inventory.add(item);
+        }
+        Some(message)
+    }
+
     pub fn look(&mut self, direction: Direction) {
         self.looking = direction;
     }
@@ -124,6 +329,78 @@ impl<'a> Player {
         self.inventory.add(item);
     }
 
+    /// Take an item out of the inventory, the hand can't be dropped.
+    pub fn drop_item(&mut self, index: usize) -> Option<ItemKind> {
+        if matches!(self.inventory.get(index), ItemKind::Hand(_)) {
+            return None;
+        }
+        Some(self.take_out(index))
+    }
+
+    /// Move an item from the inventory to its slot, what was there goes back to the inventory.
+    pub fn equip(&mut self, index: usize) -> Option<Slot> {
+        let slot = self.inventory.get(index).slot()?;
+        let item = self.take_out(index);
+        if let Some(previous) = self.equipment.put(slot, item) {
+            self.inventory.add(previous);
+        }
+        Some(slot)
+    }
+
+    /// Take everything carried and worn away, the hand stays.
+    pub fn strip(&mut self) -> Vec<ItemKind> {
+        let mut items = Vec::new();
+        for index in (0..self.inventory.len()).rev() {
+            if !matches!(self.inventory.get(index), ItemKind::Hand(_)) {
+                items.push(self.take_out(index));
+            }
+        }
+        for slot in Slot::ALL {
+            items.extend(self.equipment.take(slot));
+        }
+        items
+    }
+
+    /// Come back to life at (x, y), full and rested.
+    pub fn respawn(&mut self, x: i64, y: i64) {
+        self.x = x;
+        self.y = y;
+        self.moving = false;
+        self.afloat = false;
+        self.life = self.max_life;
+        self.immunity = 20;
+        self.effects = Effects::default();
+        self.hunger = MAX_HUNGER;
+        self.cause.clear();
+        self.age = 0;
+        self.kills = 0;
+    }
+
+    pub fn unequip(&mut self, slot: Slot) {
+        if let Some(item) = self.equipment.take(slot) {
+            self.inventory.add(item);
+        }
+    }
+
+    pub fn equipment(&self) -> &Equipment {
+        &self.equipment
+    }
+
+    pub fn equipment_mut(&mut self) -> &mut Equipment {
+        &mut self.equipment
+    }
+
+    /// Remove an item from the inventory, keeping hold of the one in hand.
+    fn take_out(&mut self, index: usize) -> ItemKind {
+        let item = self.inventory.remove(index);
+        if self.using == index {
+            self.using = 0;
+        } else if self.using > index {
+            self.using -= 1;
+        }
+        item
+    }
+
     pub fn draw<'b>(&'a self, ctx: &mut Context<'b>) {
         ctx.print(self.x as f64, self.y as f64, self.shape())
     }
@@ -143,7 +420,15 @@ impl<'a> Player {
             Direction::Left => "◀",
             Direction::Right => "▶",
         };
-        Span::styled(sprite, self.style)
+        if self.afloat {
+            Span::styled(sprite, self.style.bg(Color::Rgb(145, 77, 5)))
+        } else {
+            Span::styled(sprite, self.style)
+        }
+    }
+
+    pub fn is_afloat(&self) -> bool {
+        self.afloat
     }
 
     pub fn is_dead(&self) -> bool {
@@ -155,33 +440,225 @@ impl<'a> Player {
     }
 
     pub fn on_tick(&mut self, game: &mut Game) {
-        if self.moving {
-            self.step(game)
+        if self.moving && !self.effects.holds_back() {
+            self.step(game);
+            // boats go faster on open water
+            if *game.get_tile(self.x, self.y) == Terrain::DeepWater {
+                self.step(game)
+            } else if self.sprinting && !self.afloat {
+                self.step(game);
+                self.appetite += SPRINT_APPETITE;
+            }
+        }
+        self.afloat = game.get_tile(self.x, self.y).is_water();
+        if self.afloat {
+            self.effects.remove(Effect::Burning);
         }
         if self.immunity > 0 {
             self.immunity -= 1;
         }
+        let harmful = self.effects.harmful();
+        let life = self.life;
+        self.life = self.effects.on_tick(self.life, self.max_life);
+        if let Some(effect) = harmful.filter(|_| self.life < life) {
+            self.cause = format!("died {}", effect.name());
+        }
+        self.digest();
+        self.age += 1;
     }
 
-    pub fn heal(&mut self, amount: u8) {
-        self.life += amount;
-        if self.life > self.max_life {
-            self.life = self.max_life;
+    /// Get hungrier as time goes, starving hurts and a full stomach heals.
+    fn digest(&mut self) {
+        self.appetite += 1;
+        if self.appetite >= APPETITE {
+            self.appetite -= APPETITE;
+            self.hunger = self.hunger.saturating_sub(1);
+        }
+        self.digestion = (self.digestion + 1) % DIGESTION;
+        if self.digestion == 0 {
+            if self.hunger == 0 {
+                self.life = self.life.saturating_sub(1);
+                self.cause = String::from("starved to death");
+            } else if self.hunger >= WELL_FED {
+                self.heal(1);
+            }
         }
     }
 
-    pub fn hurt(&mut self, amount: u8) {
+    pub fn heal(&mut self, amount: u8) {
+        self.life = self.life.saturating_add(amount).min(self.max_life);
+    }
+
+    /// Armor takes some damage off, returns whether the hit went through.
+    pub fn hurt(&mut self, amount: u8, by: &str) -> bool {
+        let amount = amount.saturating_sub(self.equipment.defense()).max(1);
         if self.immunity == 0 {
+            self.cause = format!("slain by {}", by);
             if self.life >= amount {
                 self.life -= amount;
                 self.immunity = 20;
             } else {
                 self.life = 0
             }
+            true
+        } else {
+            false
         }
     }
 
+    /// Get pushed up to `distance` tiles away, stopping before anything in the way.
+    pub fn knock_back(&mut self, direction: Direction, distance: i64, game: &Game) {
+        let (dx, dy) = direction.offset();
+        for _ in 0..distance {
+            if !game.is_available(self.x + dx, self.y + dy) {
+                break;
+            }
+            self.x += dx;
+            self.y += dy;
+        }
+    }
+
+    pub fn afflict(&mut self, effect: Effect, duration: u16) {
+        self.effects.add(effect, duration);
+    }
+
+    pub fn effects(&self) -> &Effects {
+        &self.effects
+    }
+
+    pub fn life(&self) -> u8 {
+        self.life
+    }
+
+    pub fn max_life(&self) -> u8 {
+        self.max_life
+    }
+
+    pub fn set_life(&mut self, life: u8, max_life: u8) {
+        self.max_life = max_life;
+        self.life = life.min(max_life);
+    }
+
+    pub fn cause(&self) -> &str {
+        &self.cause
+    }
+
+    pub fn age(&self) -> u32 {
+        self.age
+    }
+
+    pub fn kills(&self) -> u32 {
+        self.kills
+    }
+
+    /// Count a creature slain and gather the experience it was worth.
+    pub fn add_kill(&mut self, xp: u32) -> Option<String> {
+        self.kills += 1;
+        self.gain_xp(xp)
+    }
+
+    /// Gather experience, every level reached raises the max life. Returns the level reached.
+    pub fn gain_xp(&mut self, xp: u32) -> Option<String> {
+        let before = self.experience.level();
+        let levels = self.experience.gain(xp);
+        if levels == 0 {
+            return None;
+        }
+        let extra = LIFE_PER_LEVEL.saturating_mul(levels as u8);
+        self.max_life = self.max_life.saturating_add(extra);
+        self.heal(extra);
+        let mut message = format!("you reached level {}", self.experience.level());
+        for perk in self.experience.perks().filter(|perk| perk.level() > before) {
+            message = format!("{}, {} unlocked", message, perk.name());
+        }
+        Some(message)
+    }
+
+    pub fn experience(&self) -> &Experience {
+        &self.experience
+    }
+
+    pub fn set_experience(&mut self, experience: Experience) {
+        self.experience = experience;
+    }
+
+    pub fn set_stats(&mut self, age: u32, kills: u32) {
+        self.age = age;
+        self.kills = kills;
+    }
+
+    pub fn hunger(&self) -> u8 {
+        self.hunger
+    }
+
+    pub fn set_hunger(&mut self, hunger: u8) {
+        self.hunger = hunger.min(MAX_HUNGER);
+    }
+
+    pub fn hunger_ratio(&self) -> f64 {
+        (self.hunger as f64) / (MAX_HUNGER as f64)
+    }
+
     pub fn life_ratio(&self) -> f64 {
         (self.life as f64) / (self.max_life as f64)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{items::{campfire::Campfire, meat::Meat}, chunk::CHUNK_SIZE};
+
+    #[test]
+    fn healing_stops_at_max_life_without_overflowing() {
+        let mut player = Player::new(0, 0);
+        player.set_life(250, 255);
+        player.heal(10);
+        assert_eq!(player.life(), 255);
+        player.set_life(250, 252);
+        player.heal(10);
+        assert_eq!(player.life(), 252);
+    }
+
+    #[test]
+    fn a_crafted_campfire_can_be_set_up_and_cooked_on() {
+        let mut game = Game::new(42);
+        let (x, y) = game.spawn_point();
+        let mut player = Player::new(x, y);
+        let direction = [Direction::Up, Direction::Down, Direction::Left, Direction::Right]
+            .into_iter()
+            .find(|direction| game.is_available(x + direction.offset().0, y + direction.offset().1))
+            .unwrap();
+        let (fx, fy) = (x + direction.offset().0, y + direction.offset().1);
+        player.look(direction);
+        player.inventory().add(ItemKind::Campfire(Campfire::new(1)));
+        let last = player.inventory().len() - 1;
+        player.set_using(last);
+        player.on_space(&mut game);
+        assert!(matches!(game.get_block(fx, fy), Some(BlockKind::Campfire(_))));
+
+        player.inventory().add(ItemKind::Meat(Meat::new(1)));
+        let last = player.inventory().len() - 1;
+        player.set_using(last);
+        player.on_space(&mut game);
+        let inventory = player.inventory();
+        assert!((0..inventory.len()).any(|i| matches!(inventory[i], ItemKind::CookedMeat(_))));
+    }
+
+    #[test]
+    fn a_boat_anywhere_in_the_inventory_keeps_sailing() {
+        // a world with a shore near the origin, water east of dry land
+        let (mut game, (x, y)) = (0..100).find_map(|seed| {
+            let mut game = Game::new(seed);
+            game.update_chunks();
+            let shore = (1..2 * CHUNK_SIZE).flat_map(|x| (0..2 * CHUNK_SIZE).map(move |y| (x, y)))
+                .find(|&(x, y)| game.is_sailable(x, y) && game.is_available(x - 1, y));
+            shore.map(|shore| (game, shore))
+        }).unwrap();
+        let mut player = Player::new(x - 1, y);
+        player.inventory().add(ItemKind::Boat(Boat::new(1)));
+        player.look(Direction::Right);
+        player.step(&mut game);
+        assert_eq!((player.x, player.y), (x, y));
+    }
+}
@@ -0,0 +1,101 @@
+use std::cell::{Cell, RefCell};
+use crate::{blocks::BlockKind, game::Game};
+
+use super::{is_creature, Action, Body, EntityId};
+
+/// What a projectile runs into.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Impact {
+    Wood,
+    Stone,
+    Water,
+}
+
+impl Impact {
+    /// What stands on the tile in the way of projectiles, low blocks are flown over.
+    pub fn at(game: &Game, x: i64, y: i64) -> Option<Self> {
+        match game.get_block(x, y) {
+            Some(BlockKind::Tree(_) | BlockKind::Chest(_)) => Some(Impact::Wood),
+            Some(BlockKind::Rock(_) | BlockKind::IronOre(_) | BlockKind::GoldOre(_) | BlockKind::CoalOre(_)) => Some(Impact::Stone),
+            _ if game.get_tile(x, y).is_water() => Some(Impact::Water),
+            _ => None,
+        }
+    }
+}
+
+/// Called when a projectile runs into something solid, with the tile it stopped on.
+pub type OnImpact = fn(Impact, (i64, i64)) -> Action;
+
+/// Straight flight shared by everything thrown or shot.
+pub struct Projectile {
+    /// Tiles flown each tick.
+    speed: i64,
+    /// Tiles flown before falling down.
+    range: i64,
+    /// Creatures gone through before stopping, 0 stops on the first one.
+    piercing: usize,
+    damage: u8,
+    on_impact: OnImpact,
+    flown: Cell<i64>,
+    hits: RefCell<Vec<EntityId>>,
+    spent: Cell<bool>,
+}
+
+impl Projectile {
+    pub fn new(speed: i64, range: i64, piercing: usize, damage: u8, on_impact: OnImpact) -> Self {
+        Self {
+            speed,
+            range,
+            piercing,
+            damage,
+            on_impact,
+            flown: Cell::new(0),
+            hits: RefCell::new(Vec::new()),
+            spent: Cell::new(false),
+        }
+    }
+
+    pub fn damage(&self) -> u8 {
+        self.damage
+    }
+
+    pub fn boost(&mut self, bonus: u8) {
+        self.damage = self.damage.saturating_add(bonus);
+    }
+
+    /// Done flying, the entity carrying it should go.
+    pub fn is_spent(&self) -> bool {
+        self.spent.get()
+    }
+
+    /// Fly ahead along the body's facing, hitting the first creature or obstacle on the way.
+    pub fn fly(&self, body: &Body, game: &Game) -> Action {
+        let (dx, dy) = body.looking.offset();
+        let mut tile = (body.x, body.y);
+        for step in 0..=self.speed {
+            let next = (body.x + dx * step, body.y + dy * step);
+            if let Some(impact) = Impact::at(game, next.0, next.1) {
+                self.spent.set(true);
+                return (self.on_impact)(impact, tile);
+            }
+            let target = game.entities_within(next.0, next.1, 0).into_iter().find(|&id| {
+                id != body.id
+                    && !self.hits.borrow().contains(&id)
+                    && game.get_entity(id).is_some_and(|e| is_creature(e.name()))
+            });
+            if let Some(id) = target {
+                let mut hits = self.hits.borrow_mut();
+                hits.push(id);
+                self.spent.set(hits.len() > self.piercing);
+                return Action::Attack(id, self.damage, body.looking.to_owned());
+            }
+            tile = next;
+            if self.flown.get() + step >= self.range {
+                self.spent.set(true);
+                break;
+            }
+        }
+        self.flown.set(self.flown.get() + (tile.0 - body.x).abs() + (tile.1 - body.y).abs());
+        Action::Move(tile.0, tile.1)
+    }
+}
@@ -2,37 +2,31 @@ use tui::{
     style::{Color, Style},
     text::Span,
 };
-use crate::{entities::{Direction, Entity}, game::Game};
+use crate::{entities::{Body, Direction, Entity}, game::Game};
 
 use super::{player::Player, Action};
 
 pub struct Swing {
-    x: i64,
-    y: i64,
-    looking: Direction,
-    life: u8,
+    body: Body,
     damage: u8,
 }
 
-impl<'a> Swing {
+impl Swing {
     pub fn new(x: i64, y: i64, direction: Direction, damage: u8) -> Self {
         Self {
-            x,
-            y,
-            looking: direction,
-            life: 3,
+            body: Body::new(x, y, direction, 3),
             damage,
         }
     }
 }
 
-impl<'a> Entity<'a> for Swing {
-    fn name<'b>(&self) -> &'b str {
+impl Entity for Swing {
+    fn name(&self) -> &'static str {
         "swing"
     }
 
-    fn shape(&self) -> Span<'a> {
-        match self.looking {
+    fn shape(&self) -> Span<'static> {
+        match self.body.looking {
             Direction::Up => Span::styled("-", Style::default().fg(Color::White)),
             Direction::Down => Span::styled("-", Style::default().fg(Color::White)),
             Direction::Left => Span::styled("|", Style::default().fg(Color::White)),
@@ -40,48 +34,36 @@ impl<'a> Entity<'a> for Swing {
         }
     }
 
-    fn go(&mut self, x: i64, y: i64) {
-        self.x = x;
-        self.y = y;
-    }
-
     fn on_tick(&mut self) {
-        if self.life > 0 {
-            self.life -= 1;
+        if self.body.life > 0 {
+            self.body.life -= 1;
         }
     }
 
     fn on_action(&self, _player: &mut Player, game: &Game) -> Action {
-        if let Some(entity_id) = game.get_entity_id(self.x, self.y) {
-            Action::Attack(entity_id, self.damage)
+        // only creatures are hit, not items lying on the ground nor other swings
+        let target = game.get_creature_id(self.body.x, self.body.y);
+        if let Some(entity_id) = target {
+            Action::Attack(entity_id, self.damage, self.looking())
         } else {
             Action::Nothing
         }
     }
 
-    fn is_dead(&self) -> bool {
-        self.life == 0
-    }
-
-    fn looking(&mut self) -> Direction {
-        self.looking.to_owned()
+    fn body(&self) -> &Body {
+        &self.body
     }
 
-    fn x(&self) -> i64 {
-        self.x
+    fn body_mut(&mut self) -> &mut Body {
+        &mut self.body
     }
 
-    fn y(&self) -> i64 {
-        self.y
-    }
-
-    fn heal(&mut self, _amount: u8) {}
     fn hurt(&mut self, _amount: u8) {}
 
-    fn is_harmful(&self) -> bool {
+    fn wears_tool(&self) -> bool {
         true
     }
-    
+
     fn damage(&self) -> u8 {
         self.damage
     }
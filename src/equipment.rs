@@ -0,0 +1,86 @@
+use tui::text::{Span, Spans};
+use crate::items::ItemKind;
+
+/// Where an item can be worn or held.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Slot {
+    Head,
+    Body,
+    MainHand,
+    Offhand,
+}
+
+impl Slot {
+    pub const ALL: [Slot; 4] = [Slot::Head, Slot::Body, Slot::MainHand, Slot::Offhand];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Slot::Head => "head",
+            Slot::Body => "body",
+            Slot::MainHand => "main hand",
+            Slot::Offhand => "offhand",
+        }
+    }
+
+    /// Name of the slot in save files.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Slot::Head => "head",
+            Slot::Body => "body",
+            Slot::MainHand => "main_hand",
+            Slot::Offhand => "offhand",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Slot::ALL.into_iter().find(|slot| slot.key() == key)
+    }
+}
+
+/// What the player wears and holds besides the inventory.
+#[derive(Default)]
+pub struct Equipment([Option<ItemKind>; 4]);
+
+impl Equipment {
+    pub fn get(&self, slot: Slot) -> Option<&ItemKind> {
+        self.0[slot as usize].as_ref()
+    }
+
+    pub fn get_mut(&mut self, slot: Slot) -> Option<&mut ItemKind> {
+        self.0[slot as usize].as_mut()
+    }
+
+    /// Put an item in its slot, returns the one that was there.
+    pub fn put(&mut self, slot: Slot, item: ItemKind) -> Option<ItemKind> {
+        self.0[slot as usize].replace(item)
+    }
+
+    pub fn take(&mut self, slot: Slot) -> Option<ItemKind> {
+        self.0[slot as usize].take()
+    }
+
+    /// Damage taken off every hit by everything worn.
+    pub fn defense(&self) -> u8 {
+        self.0.iter().flatten().map(|item| item.defense()).sum()
+    }
+
+    /// A line per slot with what is in it.
+    pub fn to_spans(&self) -> Vec<Spans<'static>> {
+        let mut lines = Vec::new();
+        for (i, slot) in Slot::ALL.iter().enumerate() {
+            let mut spans = vec![Span::from(format!("{} {}: ", i + 1, slot.name()))];
+            match self.get(*slot) {
+                Some(item) => {
+                    spans.push(item.shape());
+                    spans.push(Span::from(format!(" {} x{}", item.full_name(), item.quantity())));
+                    if item.defense() > 0 {
+                        spans.push(Span::from(format!(" +{}", item.defense())));
+                    }
+                },
+                None => spans.push(Span::from("-")),
+            }
+            lines.push(Spans::from(spans));
+        }
+        lines
+    }
+}
@@ -0,0 +1,113 @@
+/// Experience needed to go from the first level to the second, each level after needs this much more.
+const XP_STEP: u32 = 20;
+pub const MAX_LEVEL: u32 = 15;
+/// Max life gained with every level.
+pub const LIFE_PER_LEVEL: u8 = 10;
+/// Extra damage dealt by arrows of marksmen and blows of brawlers.
+pub const MARKSMAN_DAMAGE: u8 = 3;
+pub const BRAWLER_DAMAGE: u8 = 2;
+
+/// Bonus unlocked once the player reaches its level.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Perk {
+    /// Blocks give twice as much per swing.
+    Miner,
+    /// Arrows hit harder.
+    Marksman,
+    /// Blows dealt up close hit harder.
+    Brawler,
+}
+
+impl Perk {
+    pub const ALL: [Perk; 3] = [Perk::Miner, Perk::Marksman, Perk::Brawler];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Perk::Miner => "miner",
+            Perk::Marksman => "marksman",
+            Perk::Brawler => "brawler",
+        }
+    }
+
+    pub fn level(&self) -> u32 {
+        match self {
+            Perk::Miner => 3,
+            Perk::Marksman => 5,
+            Perk::Brawler => 8,
+        }
+    }
+}
+
+/// Level of the player with the experience gathered towards the next one.
+pub struct Experience {
+    level: u32,
+    xp: u32,
+}
+
+impl Default for Experience {
+    fn default() -> Self {
+        Self { level: 1, xp: 0 }
+    }
+}
+
+impl Experience {
+    /// Experience beyond what the level needs is dropped, so the bar never overflows.
+    pub fn new(level: u32, xp: u32) -> Self {
+        let mut experience = Self { level: level.clamp(1, MAX_LEVEL), xp: 0 };
+        experience.xp = if experience.level == MAX_LEVEL { 0 } else { xp.min(experience.needed() - 1) };
+        experience
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    pub fn xp(&self) -> u32 {
+        self.xp
+    }
+
+    /// Experience needed to reach the next level.
+    pub fn needed(&self) -> u32 {
+        XP_STEP * self.level
+    }
+
+    /// Gather experience, returns how many levels were gained. None is kept at the last level.
+    pub fn gain(&mut self, amount: u32) -> u32 {
+        let before = self.level;
+        self.xp += amount;
+        while self.level < MAX_LEVEL && self.xp >= self.needed() {
+            self.xp -= self.needed();
+            self.level += 1;
+        }
+        if self.level == MAX_LEVEL {
+            self.xp = 0;
+        }
+        self.level - before
+    }
+
+    /// Part of the way to the next level, full once the last one is reached.
+    pub fn ratio(&self) -> f64 {
+        if self.level == MAX_LEVEL {
+            1.0
+        } else {
+            (self.xp as f64) / (self.needed() as f64)
+        }
+    }
+
+    /// Progress shown on the experience bar.
+    pub fn label(&self) -> String {
+        if self.level == MAX_LEVEL {
+            String::from("max level")
+        } else {
+            format!("{}/{} xp", self.xp, self.needed())
+        }
+    }
+
+    pub fn has(&self, perk: Perk) -> bool {
+        self.level >= perk.level()
+    }
+
+    pub fn perks(&self) -> impl Iterator<Item = Perk> + '_ {
+        Perk::ALL.into_iter().filter(|perk| self.has(*perk))
+    }
+}
@@ -1,6 +1,5 @@
 use crossterm::event::{self, Event, KeyCode};
-use perlin2d::PerlinNoise2D;
-use rand::{thread_rng, Rng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use tui::{
     Frame,
     symbols,
@@ -8,54 +7,79 @@ use tui::{
     backend::Backend,
     style::{Style, Color},
     layout::{Layout, Constraint},
-    widgets::{Block, Borders, Paragraph, canvas::Canvas, Gauge, List, ListState}
+    widgets::{Block, Borders, Paragraph, canvas::Canvas, Gauge, List, ListState},
+    text::Spans
 };
 use std::{
     io,
+    mem,
+    collections::{HashMap, HashSet},
     time::{Duration, Instant}
 };
 use crate::{entities::{
-    EntityKind,
-    player::Player, Direction, Action
-}, blocks::BlockKind, chunk::{Chunk, CHUNK_SIZE, Terrain}, ui::{inventory, crafting, map}};
+    EntityKind, EntityId,
+    player::Player, itemdrop::ItemDrop, Direction, Action, KNOCKBACK, BACKSTAB, is_creature
+}, items::ItemKind, biome::WorldNoise, blocks::{BlockKind, Block as _, hole::Hole, ladder::Ladder, grave::Grave}, layer::{Depth, Layer}, chunk::{Chunk, ChunkDelta, Terrain, chunk_coords}, structure::Structure, loot::LootTable, spatial::SpatialIndex, rules::{Rules, DeathRule}, effects::Effect, ui::{inventory, crafting, map, game_over}, save};
 
 const TITLE: &str = "Yuni-Kod";
+/// Chunks further from the origin than this never fit on the map, they are not remembered
+/// so that exploring does not grow the memory used forever.
+const MAP_RANGE: i64 = 128;
+/// How far east of the origin land to spawn on is looked for,
+/// and how far from that land a free tile is.
+const SPAWN_SEARCH: i64 = 4096;
+const SPAWN_REACH: i64 = 24;
+/// How far from where the player died a grave may be dug.
+const GRAVE_REACH: i64 = 3;
 
 pub struct Game {
     should_quit: bool,
     entities: Vec<EntityKind>,
-    loaded_chunks: Vec<Chunk>,
-    unused_chunks: Vec<Chunk>,
+    entity_slots: HashMap<EntityId, usize>,
+    spatial: SpatialIndex,
+    next_entity_id: u64,
+    loaded_chunks: HashMap<(i64, i64), Chunk>,
+    chunk_deltas: HashMap<(i64, i64), ChunkDelta>,
+    explored: HashMap<(i64, i64), Terrain>,
+    populated: HashSet<(i64, i64)>,
+    /// What lies in the graves of the player, on every level.
+    graves: HashMap<(Depth, i64, i64), Vec<ItemKind>>,
+    rules: Rules,
+    depth: Depth,
+    parked: HashMap<Depth, Layer>,
     offset: (i64, i64),
     x_bounds: i64,
     y_bounds: i64,
-    perlin: PerlinNoise2D,
+    seed: u32,
+    noise: WorldNoise,
+    rng: StdRng,
     message: String,
     message_timer: u8
 }
 
-impl<'a> Game {
-    pub fn new() -> Self {
-
-        let perlin = PerlinNoise2D::new(
-            1,
-            100.0,
-            5.0, // 5.0
-            1.0,
-            2.0,
-            (100.0, 100.0),
-            0.5,
-            10
-        );
+impl Game {
+    pub fn new(seed: u32) -> Self {
+
         Game {
             should_quit: false,
             entities: Vec::new(),
-            loaded_chunks: Vec::new(),
-            unused_chunks: Vec::new(),
+            entity_slots: HashMap::new(),
+            spatial: SpatialIndex::default(),
+            next_entity_id: 1,
+            loaded_chunks: HashMap::new(),
+            chunk_deltas: HashMap::new(),
+            explored: HashMap::new(),
+            populated: HashSet::new(),
+            graves: HashMap::new(),
+            rules: Rules::default(),
+            depth: Depth::Surface,
+            parked: HashMap::new(),
             offset: (0, 0),
             x_bounds: 0,
             y_bounds: 0,
-            perlin,
+            seed,
+            noise: WorldNoise::new(seed),
+            rng: StdRng::seed_from_u64(seed as u64),
             message: String::new(),
             message_timer: 0
         }
@@ -69,20 +93,191 @@ impl<'a> Game {
         &self.entities
     }
 
-    pub fn mut_entities(&mut self) -> &mut Vec<EntityKind> {
-        &mut self.entities
+    pub fn get_entity(&self, id: EntityId) -> Option<&EntityKind> {
+        self.entity_slots.get(&id).map(|&slot| &self.entities[slot])
+    }
+
+    pub fn get_mut_entity(&mut self, id: EntityId) -> Option<&mut EntityKind> {
+        self.entity_slots.get(&id).map(|&slot| &mut self.entities[slot])
+    }
+
+    /// Add an entity to the current level.
+    pub fn add_entity(&mut self, mut entity: EntityKind) {
+        self.assign_id(&mut entity);
+        self.entity_slots.insert(entity.id(), self.entities.len());
+        self.spatial.insert(entity.id(), entity.x(), entity.y());
+        self.entities.push(entity);
+    }
+
+    fn assign_id(&mut self, entity: &mut EntityKind) {
+        entity.body_mut().id = EntityId(self.next_entity_id);
+        self.next_entity_id += 1;
+    }
+
+    pub fn remove_entity(&mut self, id: EntityId) -> Option<EntityKind> {
+        let slot = self.entity_slots.remove(&id)?;
+        let entity = self.entities.swap_remove(slot);
+        self.spatial.remove(id, entity.x(), entity.y());
+        // the last entity took the place of the removed one
+        if let Some(moved) = self.entities.get(slot) {
+            self.entity_slots.insert(moved.id(), slot);
+        }
+        Some(entity)
+    }
+
+    /// Take every item lying on the tile off the ground.
+    pub fn pick_up_at(&mut self, x: i64, y: i64) -> Vec<ItemKind> {
+        let mut items = Vec::new();
+        for id in self.entities_within(x, y, 0) {
+            if let Some(item) = self.get_mut_entity(id).and_then(|e| e.take_item()) {
+                self.remove_entity(id);
+                items.push(item);
+            }
+        }
+        items
+    }
+
+    /// Leave an item on the ground.
+    pub fn drop_item(&mut self, x: i64, y: i64, item: ItemKind) {
+        self.add_entity(Box::new(ItemDrop::new(x, y, item)));
+    }
+
+    /// Index a whole new list of entities, when changing levels.
+    fn reindex_entities(&mut self) {
+        self.entity_slots = self.entities.iter().enumerate().map(|(slot, e)| (e.id(), slot)).collect();
+        self.spatial = SpatialIndex::new(&self.entities);
     }
 
-    pub fn loaded_chunks(&self) -> &Vec<Chunk> {
+    pub fn loaded_chunks(&self) -> &HashMap<(i64, i64), Chunk> {
         &self.loaded_chunks
     }
 
-    pub fn unused_chunks(&self) -> &Vec<Chunk> {
-        &self.unused_chunks
+    /// Changes made to chunks that are not loaded anymore.
+    pub fn chunk_deltas(&self) -> &HashMap<(i64, i64), ChunkDelta> {
+        &self.chunk_deltas
+    }
+
+    /// Average terrain of every chunk visited so far, used by the map.
+    pub fn explored(&self) -> &HashMap<(i64, i64), Terrain> {
+        &self.explored
+    }
+
+    /// Chunks whose structure inhabitants were already spawned.
+    pub fn populated(&self) -> &HashSet<(i64, i64)> {
+        &self.populated
+    }
+
+    pub fn depth(&self) -> Depth {
+        self.depth
+    }
+
+    /// The levels of the world the player is not on.
+    pub fn parked_layers(&self) -> &HashMap<Depth, Layer> {
+        &self.parked
+    }
+
+    /// Move to another level of the world, the current one is unloaded
+    /// and kept aside until the player comes back.
+    pub fn change_depth(&mut self, depth: Depth) {
+        if depth == self.depth {
+            return;
+        }
+        let keys: Vec<(i64, i64)> = self.loaded_chunks.keys().copied().collect();
+        for key in keys {
+            self.unload_chunk(key);
+        }
+        let layer = Layer {
+            chunk_deltas: mem::take(&mut self.chunk_deltas),
+            explored: mem::take(&mut self.explored),
+            entities: mem::take(&mut self.entities),
+            populated: mem::take(&mut self.populated),
+        };
+        self.parked.insert(self.depth, layer);
+
+        let layer = self.parked.remove(&depth).unwrap_or_default();
+        self.chunk_deltas = layer.chunk_deltas;
+        self.explored = layer.explored;
+        self.entities = layer.entities;
+        self.reindex_entities();
+        self.populated = layer.populated;
+        self.depth = depth;
+        self.update_chunks();
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    pub fn set_rules(&mut self, rules: Rules) {
+        self.rules = rules;
+    }
+
+    /// Free dry tile next to the first land east of the origin, where the player starts
+    /// and comes back to life. The view moves there to load its chunks, rivers and blocks
+    /// are only known then. The land itself is the fallback when no tile around it is free.
+    pub fn spawn_point(&mut self) -> (i64, i64) {
+        let land = (0..SPAWN_SEARCH)
+            .find(|&x| self.noise.height.get_noise(x as f64, 0.0) >= 0.0)
+            .unwrap_or_default();
+        self.offset = (land, 0);
+        self.update_chunks();
+        let mut around: Vec<(i64, i64)> = (-SPAWN_REACH..=SPAWN_REACH)
+            .flat_map(|dx| (-SPAWN_REACH..=SPAWN_REACH).map(move |dy| (land + dx, dy)))
+            .collect();
+        around.sort_by_key(|&(x, y)| (x - land).abs().max(y.abs()));
+        around.into_iter()
+            .find(|&(x, y)| self.loaded_chunks.contains_key(&chunk_coords(x, y).0) && self.is_available(x, y))
+            .unwrap_or((land, 0))
+    }
+
+    pub fn graves(&self) -> &HashMap<(Depth, i64, i64), Vec<ItemKind>> {
+        &self.graves
     }
 
-    pub fn perlin(&self) -> &PerlinNoise2D {
-        &self.perlin
+    /// Put an item in the grave on a tile of any level, the grave itself is a block.
+    pub fn add_to_grave(&mut self, depth: Depth, x: i64, y: i64, item: ItemKind) {
+        self.graves.entry((depth, x, y)).or_default().push(item);
+    }
+
+    /// Bury items in a grave on the current level, on the nearest tile without a block,
+    /// or else without stairs. They are left on the ground when there is no such tile.
+    pub fn bury(&mut self, x: i64, y: i64, items: Vec<ItemKind>) {
+        if items.is_empty() {
+            return;
+        }
+        let mut around: Vec<(i64, i64)> = (-GRAVE_REACH..=GRAVE_REACH)
+            .flat_map(|dx| (-GRAVE_REACH..=GRAVE_REACH).map(move |dy| (x + dx, y + dy)))
+            .collect();
+        around.sort_by_key(|&(i, j)| (i - x).abs().max((j - y).abs()));
+        // stairs are never covered, they are the only way between the levels
+        let stairs = |block: &BlockKind| matches!(block, BlockKind::Hole(_) | BlockKind::Ladder(_));
+        let free = around.iter().copied().find(|&(i, j)| self.get_block(i, j).is_none())
+            .or_else(|| around.iter().copied().find(|&(i, j)| !self.get_block(i, j).is_some_and(stairs)));
+        let Some((x, y)) = free else {
+            for item in items {
+                self.drop_item(x, y, item);
+            }
+            return;
+        };
+        let grave = Grave::generate(&mut self.rng);
+        self.set_block(x, y, Some(grave));
+        for item in items {
+            self.add_to_grave(self.depth, x, y, item);
+        }
+    }
+
+    /// Dig a grave of the current level up, returns what was in it.
+    pub fn exhume(&mut self, x: i64, y: i64) -> Vec<ItemKind> {
+        self.destroy_block(x, y);
+        self.graves.remove(&(self.depth, x, y)).unwrap_or_default()
+    }
+
+    pub fn noise(&self) -> &WorldNoise {
+        &self.noise
     }
 
     pub fn on_tick(&mut self, player: &mut Player) {
@@ -112,116 +307,292 @@ impl<'a> Game {
         }
 
         // spawn entities
-        if thread_rng().gen_ratio(1, 50) {
+        if self.rng.gen_ratio(1, 50) {
             let x_range = (-self.x_bounds+self.offset.0)..(self.x_bounds+self.offset.0);
             let y_range = (-self.y_bounds+self.offset.1)..(self.y_bounds+self.offset.1);
-            let x = thread_rng().gen_range(x_range) as i64;
-            let y = thread_rng().gen_range(y_range) as i64;
-            if let Some(entity) = self.get_tile(x, y).random_entity(x, y) {
-                self.entities.push(entity);
+            let x = self.rng.gen_range(x_range);
+            let y = self.rng.gen_range(y_range);
+            let terrain = *self.get_tile(x, y);
+            let entity = terrain.random_entity(x, y, &mut self.rng);
+            if let Some(entity) = entity.filter(|_| self.is_available(x, y)) {
+                self.add_entity(entity);
             }
         }
 
         // update entities
         for i in 0..self.entities.len() {
+            let before = self.entities[i].position();
             let action = self.entities[i].on_action(player, self);
             match action {
                 Action::Move(x, y) => self.entities[i].go(x, y),
-                Action::Spawn(mut entities) => self.entities.append(&mut entities),
-                Action::Attack(id, damage) => {
-                    let target = &mut self.entities[id];
-                    target.hurt(damage);
-                    self.set_message(format!("{} took {} damage", id, damage));
+                Action::Spawn(entities) => {
+                    for entity in entities {
+                        self.add_entity(entity);
+                    }
+                },
+                Action::Attack(id, damage, direction) => {
+                    // the target may have died earlier this tick
+                    let alive = self.get_entity(id).is_some_and(|e| !e.is_dead());
+                    let struck = self.strike(id, damage, direction);
+                    if let Some(damage) = struck {
+                        let name = self.get_entity(id).map(|e| e.name()).unwrap_or_default();
+                        self.set_message(format!("{} took {} damage", name, damage));
+                    }
+                    // swings of a tool only wear it when they land
+                    if struck.is_some() && self.entities[i].wears_tool() {
+                        if let Some(broke) = player.wear_in_hand() {
+                            self.set_message(broke);
+                        }
+                    }
+                    let slain = self.get_entity(id).filter(|e| alive && e.is_dead()).map(|e| e.xp());
+                    if let Some(level) = slain.and_then(|xp| player.add_kill(xp)) {
+                        self.set_message(level);
+                    }
                 },
                 Action::Nothing => {},
             };
+            // water puts fire out, as it does for the player
+            let (x, y) = self.entities[i].position();
+            if self.get_tile(x, y).is_water() {
+                self.entities[i].cure(Effect::Burning);
+            }
             self.entities[i].on_tick();
+            let after = self.entities[i].position();
+            if after != before {
+                self.spatial.relocate(self.entities[i].id(), before, after);
+            }
+        }
+
+        // whoever stands in fire catches it
+        let mut afflicted = Vec::new();
+        for entity in &self.entities {
+            if let Some((effect, duration)) = entity.afflicts() {
+                if player.x() == entity.x() && player.y() == entity.y() {
+                    player.afflict(effect, duration);
+                }
+                for id in self.entities_within(entity.x(), entity.y(), 0) {
+                    afflicted.push((id, effect, duration));
+                }
+            }
+        }
+        for (id, effect, duration) in afflicted {
+            if let Some(entity) = self.get_mut_entity(id).filter(|e| e.afflicts().is_none()) {
+                entity.afflict(effect, duration);
+            }
+        }
+
+        // destroy dead entities, creatures leave their loot behind
+        let mut loot = Vec::new();
+        let mut dead = Vec::new();
+        for entity in self.entities.iter().filter(|e| e.is_dead()) {
+            let item = LootTable::of(entity.name()).and_then(|table| table.roll(&mut self.rng));
+            if let Some(item) = item {
+                loot.push((entity.x(), entity.y(), item));
+            }
+            dead.push(entity.id());
+        }
+        for id in dead {
+            self.remove_entity(id);
+        }
+        for (x, y, item) in loot {
+            self.drop_item(x, y, item);
         }
-        
-        // destroy dead entities
-        self.entities.retain(|e| !e.is_dead());
     }
 
-    pub fn is_available(&self, x: i64, y: i64) -> bool {
-        self.get_entity_id(x, y).is_none() &&
-        self.get_block(x, y).is_none() &&
-        match self.get_tile(x, y) {
-            Terrain::Water => false,
-            Terrain::DeepWater => false,
-            _ => true,
+    /// Hit an entity with a blow travelling in `direction`. Hits from behind deal more
+    /// damage and those going through push the target back, returns the damage dealt
+    /// or `None` when the target was not hurt.
+    pub fn strike(&mut self, id: EntityId, damage: u8, direction: Direction) -> Option<u8> {
+        let target = self.get_mut_entity(id)?;
+        let damage = if target.looking() == direction { damage.saturating_mul(BACKSTAB) } else { damage };
+        let life = target.life();
+        target.hurt(damage);
+        // a target still immune from the last blow takes nothing
+        if target.life() >= life {
+            return None;
+        }
+        if !target.is_dead() {
+            self.knock_back(id, direction, KNOCKBACK);
         }
+        Some(damage)
     }
 
-    pub fn get_entity_id(&self, x: i64, y: i64) -> Option<usize> {
-        for i in 0..self.entities.len() {
-            if self.entities[i].collide(x, y) {
-                return Some(i);
+    /// Push an entity up to `distance` tiles away, it stops before anything it can't stand on.
+    fn knock_back(&mut self, id: EntityId, direction: Direction, distance: i64) {
+        let Some(from) = self.get_entity(id).map(|e| e.position()) else { return };
+        let (dx, dy) = direction.offset();
+        let mut to = from;
+        for _ in 0..distance {
+            if !self.is_available(to.0 + dx, to.1 + dy) {
+                break;
             }
+            to = (to.0 + dx, to.1 + dy);
+        }
+        if let Some(entity) = self.get_mut_entity(id).filter(|_| to != from) {
+            let body = entity.body_mut();
+            body.x = to.0;
+            body.y = to.1;
+            self.spatial.relocate(id, from, to);
         }
-        None
+    }
+
+    pub fn is_available(&self, x: i64, y: i64) -> bool {
+        !self.is_occupied(x, y) &&
+        self.get_block(x, y).is_none() &&
+        !self.get_tile(x, y).is_water()
+    }
+
+    /// Free water tile that can only be crossed by boat.
+    pub fn is_sailable(&self, x: i64, y: i64) -> bool {
+        !self.is_occupied(x, y) &&
+        self.get_block(x, y).is_none() &&
+        self.get_tile(x, y).is_water()
+    }
+
+    pub fn get_entity_id(&self, x: i64, y: i64) -> Option<EntityId> {
+        self.spatial.at(x, y)
+    }
+
+    /// A solid entity stands on the tile, items lying on the ground don't count.
+    fn is_occupied(&self, x: i64, y: i64) -> bool {
+        self.spatial.within(x, y, 0).any(|(id, _, _)| self.get_entity(id).is_some_and(|e| e.is_solid()))
+    }
+
+    /// A creature standing on the tile.
+    pub fn get_creature_id(&self, x: i64, y: i64) -> Option<EntityId> {
+        self.spatial.within(x, y, 0)
+            .map(|(id, _, _)| id)
+            .find(|&id| self.get_entity(id).is_some_and(|e| is_creature(e.name())))
+    }
+
+    /// Entities at most `radius` tiles away from (x, y) on both axes.
+    pub fn entities_within(&self, x: i64, y: i64, radius: i64) -> Vec<EntityId> {
+        self.spatial.within(x, y, radius).map(|(id, _, _)| id).collect()
+    }
+
+    /// Closest hostile creature at most `radius` tiles away from (x, y) on both axes.
+    pub fn nearest_hostile(&self, x: i64, y: i64, radius: i64) -> Option<EntityId> {
+        self.spatial.within(x, y, radius)
+            .filter(|(id, _, _)| self.get_entity(*id).is_some_and(|e| e.is_hostile()))
+            .min_by_key(|(_, i, j)| (i - x).abs() + (j - y).abs())
+            .map(|(id, _, _)| id)
     }
 
     pub fn get_block(&self, x: i64, y: i64) -> Option<&BlockKind> {
-        let mut chunk_idx = ( x / CHUNK_SIZE, y / CHUNK_SIZE );
-        if x < 0 && x%CHUNK_SIZE != 0 { chunk_idx.0 -= 1 }
-        if y < 0 && y%CHUNK_SIZE != 0 { chunk_idx.1 -= 1 }
-        for chunk in &self.loaded_chunks {
-            if chunk_idx == (chunk.0, chunk.1) {
-                let i = x%CHUNK_SIZE;
-                let j = y%CHUNK_SIZE;
-                let i = ((CHUNK_SIZE+i)%CHUNK_SIZE) as usize;
-                let j = ((CHUNK_SIZE+j)%CHUNK_SIZE) as usize;
-                return chunk[(i, j)].1.as_ref();
-            }
-        }
-        None
+        let (chunk_idx, tile) = chunk_coords(x, y);
+        self.loaded_chunks.get(&chunk_idx)?[tile].1.as_ref()
     }
 
     pub fn get_mut_block(&mut self, x: i64, y: i64) -> Option<&mut BlockKind> {
-        let mut chunk_idx = ( x / CHUNK_SIZE, y / CHUNK_SIZE );
-        if x < 0 && x%CHUNK_SIZE != 0 { chunk_idx.0 -= 1 }
-        if y < 0 && y%CHUNK_SIZE != 0 { chunk_idx.1 -= 1 }
-        for chunk in &mut self.loaded_chunks {
-            if chunk_idx == (chunk.0, chunk.1) {
-                let i = x%CHUNK_SIZE;
-                let j = y%CHUNK_SIZE;
-                let i = ((CHUNK_SIZE+i)%CHUNK_SIZE) as usize;
-                let j = ((CHUNK_SIZE+j)%CHUNK_SIZE) as usize;
-                return chunk[(i, j)].1.as_mut();
-            }
-        }
-        None
+        let (chunk_idx, tile) = chunk_coords(x, y);
+        let chunk = self.loaded_chunks.get_mut(&chunk_idx)?;
+        chunk.set_modified();
+        chunk[tile].1.as_mut()
     }
 
     pub fn get_tile(&self, x: i64, y: i64) -> &Terrain {
-        let mut chunk_idx = ( x / CHUNK_SIZE, y / CHUNK_SIZE );
-        if x < 0 && x%CHUNK_SIZE != 0 { chunk_idx.0 -= 1 }
-        if y < 0 && y%CHUNK_SIZE != 0 { chunk_idx.1 -= 1 }
-        for chunk in &self.loaded_chunks {
-            if chunk_idx == (chunk.0, chunk.1) {
-                let i = x%CHUNK_SIZE;
-                let j = y%CHUNK_SIZE;
-                let i = ((CHUNK_SIZE+i)%CHUNK_SIZE) as usize;
-                let j = ((CHUNK_SIZE+j)%CHUNK_SIZE) as usize;
-                return &chunk[(i, j)].0;
-            }
+        let (chunk_idx, tile) = chunk_coords(x, y);
+        match self.loaded_chunks.get(&chunk_idx) {
+            Some(chunk) => &chunk[tile].0,
+            None => &Terrain::Grass,
         }
-        &Terrain::Grass
     }
 
     pub fn destroy_block(&mut self, x: i64, y: i64) {
-        let mut chunk_idx = ( x / CHUNK_SIZE, y / CHUNK_SIZE );
-        if x < 0 { chunk_idx.0 -= 1 }
-        if y < 0 { chunk_idx.1 -= 1 }
-        for chunk in &mut self.loaded_chunks {
-            if chunk_idx == (chunk.0, chunk.1) {
-                let i = x%CHUNK_SIZE;
-                let j = y%CHUNK_SIZE;
-                let i = ((CHUNK_SIZE+i)%CHUNK_SIZE) as usize;
-                let j = ((CHUNK_SIZE+j)%CHUNK_SIZE) as usize;
-                chunk[(i, j)].1 = None;
-                return;
-            }
+        let (chunk_idx, tile) = chunk_coords(x, y);
+        if let Some(chunk) = self.loaded_chunks.get_mut(&chunk_idx) {
+            chunk[tile].1 = None;
+            chunk.set_modified();
+        }
+    }
+
+    pub fn set_block(&mut self, x: i64, y: i64, block: Option<BlockKind>) {
+        let (chunk_idx, (i, j)) = chunk_coords(x, y);
+        match self.loaded_chunks.get_mut(&chunk_idx) {
+            Some(chunk) => {
+                chunk[(i, j)].1 = block;
+                chunk.set_modified();
+            },
+            // the chunk is not loaded, keep the change for when it is rebuilt
+            None => self.chunk_deltas.entry(chunk_idx).or_default().set(i, j, block),
+        }
+    }
+
+    /// Set a block built by `make` on a free tile, returns whether there was room for it.
+    pub fn place_block(&mut self, x: i64, y: i64, make: fn(&mut StdRng) -> BlockKind) -> bool {
+        if !self.is_available(x, y) {
+            return false;
+        }
+        let block = make(&mut self.rng);
+        self.set_block(x, y, Some(block));
+        true
+    }
+
+    /// Dig stairs down to the caves in bare stone.
+    pub fn dig_hole(&mut self, x: i64, y: i64) -> bool {
+        let diggable = self.depth == Depth::Surface
+            && *self.get_tile(x, y) == Terrain::Stone
+            && self.is_available(x, y);
+        if diggable {
+            let hole = Hole::generate(&mut self.rng);
+            self.set_block(x, y, Some(hole));
+        }
+        diggable
+    }
+
+    /// Follow the stairs at the given coordinates to the other level,
+    /// returns false if there are no stairs there.
+    pub fn take_stairs(&mut self, x: i64, y: i64) -> bool {
+        match self.get_block(x, y) {
+            Some(BlockKind::Hole(_)) => {
+                self.change_depth(Depth::Caves);
+                // clear some room at the bottom of the stairs
+                for i in x-1..=x+1 {
+                    for j in y-1..=y+1 {
+                        if self.get_block(i, j).is_some() {
+                            self.set_block(i, j, None);
+                        }
+                    }
+                }
+                let ladder = Ladder::generate(&mut self.rng);
+                self.set_block(x, y, Some(ladder));
+                true
+            },
+            Some(BlockKind::Ladder(_)) => {
+                self.change_depth(Depth::Surface);
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Change a block on any level of the world.
+    pub fn set_block_at(&mut self, depth: Depth, x: i64, y: i64, block: Option<BlockKind>) {
+        if depth == self.depth {
+            self.set_block(x, y, block);
+        } else {
+            let (chunk_idx, (i, j)) = chunk_coords(x, y);
+            let layer = self.parked.entry(depth).or_default();
+            layer.chunk_deltas.entry(chunk_idx).or_default().set(i, j, block);
+        }
+    }
+
+    /// Remember that the structure inhabitants of a chunk of any level were spawned.
+    pub fn set_populated_at(&mut self, depth: Depth, chunk_idx: (i64, i64)) {
+        if depth == self.depth {
+            self.populated.insert(chunk_idx);
+        } else {
+            self.parked.entry(depth).or_default().populated.insert(chunk_idx);
+        }
+    }
+
+    /// Add an entity to any level of the world.
+    pub fn add_entity_at(&mut self, depth: Depth, mut entity: EntityKind) {
+        if depth == self.depth {
+            self.add_entity(entity);
+        } else {
+            self.assign_id(&mut entity);
+            self.parked.entry(depth).or_default().entities.push(entity);
         }
     }
 
@@ -240,38 +611,30 @@ impl<'a> Game {
     }
 
     pub fn update_chunks(&mut self) {
-        let x0 = (-self.x_bounds + self.offset.0) as i64;
-        let x1 = ( self.x_bounds + self.offset.0) as i64;
-        let y0 = (-self.y_bounds + self.offset.1) as i64;
-        let y1 = ( self.y_bounds + self.offset.1) as i64;
-        let mut c0 = ( x0 / CHUNK_SIZE, y0 / CHUNK_SIZE );
-        if x0 < 0 { c0.0 -= 1 }
-        if y0 < 0 { c0.1 -= 1 }
-        let mut c1 = ( x1 / CHUNK_SIZE + 1, y1 / CHUNK_SIZE + 1);
-        if x1 < 0 { c1.0 -= 1 }
-        if y1 < 0 { c1.1 -= 1 }
-        
+        let x0 = -self.x_bounds + self.offset.0;
+        let x1 =  self.x_bounds + self.offset.0;
+        let y0 = -self.y_bounds + self.offset.1;
+        let y1 =  self.y_bounds + self.offset.1;
+        let (c0, _) = chunk_coords(x0, y0);
+        let (mut c1, _) = chunk_coords(x1, y1);
+        c1 = (c1.0 + 1, c1.1 + 1);
+
         // load chunks
         for i in c0.0..=c1.0 {
             for j in c0.1..=c1.1 {
-                let mut found = false;
-                for chunk in &self.loaded_chunks {
-                    if chunk.0 == i && chunk.1 == j {
-                        found = true;
-                        break
+                if !self.loaded_chunks.contains_key(&(i, j)) {
+                    // rebuild chunk from the seed and its changes
+                    let mut chunk = Chunk::generate(self.depth, i, j, &self.noise, self.seed);
+                    if let Some(delta) = self.chunk_deltas.remove(&(i, j)) {
+                        chunk.apply(delta);
                     }
-                }
-                if !found { // check in unused chunks
-                    for idx in 0..self.unused_chunks.len() {
-                        if self.unused_chunks[idx].0 == i && self.unused_chunks[idx].1 == j {
-                            self.loaded_chunks.push(self.unused_chunks.swap_remove(idx));
-                            found = true;
-                            break
-                        }
+                    if i.abs() <= MAP_RANGE && j.abs() <= MAP_RANGE {
+                        self.explored.entry((i, j)).or_insert_with(|| chunk.average_terrain());
+                    }
+                    self.loaded_chunks.insert((i, j), chunk);
+                    if !self.populated.contains(&(i, j)) {
+                        self.populate(i, j);
                     }
-                }
-                if !found { // create chunk
-                    self.loaded_chunks.push(Chunk::new(i, j, &self.perlin))
                 }
             }
         }
@@ -279,11 +642,41 @@ impl<'a> Game {
         // unload chunks
         let n_range = c0.0..=c1.0;
         let m_range = c0.1..=c1.1;
-        let mut idx = 0;
-        while idx < self.loaded_chunks.len() {
-            if !n_range.contains(&(self.loaded_chunks[idx].0)) || !m_range.contains(&(self.loaded_chunks[idx].1)) {
-                self.unused_chunks.push(self.loaded_chunks.swap_remove(idx));
-            } else { idx += 1 }
+        let out_of_view: Vec<(i64, i64)> = self.loaded_chunks
+            .keys()
+            .filter(|(i, j)| !n_range.contains(i) || !m_range.contains(j))
+            .copied()
+            .collect();
+        for key in out_of_view {
+            self.unload_chunk(key);
+        }
+    }
+
+    /// Spawn the creatures of the structure lying in the chunk,
+    /// they only come once so killing them clears the structure for good.
+    fn populate(&mut self, col: i64, row: i64) {
+        let Some(structure) = Structure::of_chunk(self.depth, col, row, &self.noise, self.seed) else { return };
+        let inhabitants: Vec<EntityKind> = structure.inhabitants(col, row)
+            .into_iter()
+            .filter(|entity| !self.get_tile(entity.x(), entity.y()).is_water())
+            .collect();
+        if !inhabitants.is_empty() {
+            self.populated.insert((col, row));
+            for entity in inhabitants {
+                self.add_entity(entity);
+            }
+        }
+    }
+
+    fn unload_chunk(&mut self, key: (i64, i64)) {
+        let Some(chunk) = self.loaded_chunks.remove(&key) else { return };
+        // clean chunks can be generated again, drop them entirely
+        if chunk.is_modified() {
+            let baseline = Chunk::generate(self.depth, key.0, key.1, &self.noise, self.seed);
+            let delta = chunk.into_delta(&baseline);
+            if !delta.is_empty() {
+                self.chunk_deltas.insert(key, delta);
+            }
         }
     }
 }
@@ -292,7 +685,7 @@ fn on_key<B: Backend>(terminal: &mut Terminal<B>, game: &mut Game, player: &mut
     match c {
         ' ' | 'k' => {
             if let Some(entity) = player.on_space(game) {
-                game.entities.push(entity);
+                game.add_entity(entity);
             }
         }
         'q' => game.should_quit = true,
@@ -347,15 +740,48 @@ pub fn run<B: Backend>(terminal: &mut Terminal<B>, mut game: Game, mut player: P
             game.update_chunks();
             last_tick = Instant::now();
         }
+        if player.is_dead() {
+            let hardcore = game.rules().hardcore;
+            if hardcore {
+                save::delete(game.seed())?;
+            }
+            let again = game_over::run(terminal, &player, hardcore)?;
+            if hardcore {
+                return Ok(());
+            }
+            // the death rule applies whether the player carries on now or later
+            respawn(&mut game, &mut player);
+            if !again {
+                return save::save(&game, &mut player);
+            }
+        }
         terminal.draw(|frame| draw(frame, &mut game, &mut player))?;
 
         if game.should_quit {
-            return Ok(());
+            return save::save(&game, &mut player);
         }
     }
 }
 
-fn draw<'a, B: Backend>(frame: &mut Frame<B>, game: &mut Game, player: &mut Player) {
+/// Bring the player back to life at the spawn point, what they carried follows the death rule.
+fn respawn(game: &mut Game, player: &mut Player) {
+    match game.rules().on_death {
+        DeathRule::Keep => {},
+        DeathRule::Grave => {
+            let items = player.strip();
+            game.bury(player.x(), player.y(), items);
+        },
+        DeathRule::LoseAll => {
+            player.strip();
+        },
+    }
+    game.change_depth(Depth::Surface);
+    let (x, y) = game.spawn_point();
+    player.respawn(x, y);
+    game.update_chunks();
+}
+
+fn draw<B: Backend>(frame: &mut Frame<B>, game: &mut Game, player: &mut Player) {
     let vchunks = Layout::default()
         .constraints([Constraint::Length(3), Constraint::Min(2), Constraint::Length(3)])
         .split(frame.size());
@@ -367,16 +793,48 @@ fn draw<'a, B: Backend>(frame: &mut Frame<B>, game: &mut Game, player: &mut Play
 
     // controls information \\
     //let text = format!("x:{} y:{} p:{}", player.x(), player.y(), game.perlin.get_noise(player.x() as f64, player.y() as f64));
-    let text = format!("nbr of loaded chunks:{} | nbr of unused chunks:{} | x:{} | y:{}", game.loaded_chunks.len(), game.unused_chunks.len(), player.x(), player.y());
+    let place = match game.depth {
+        Depth::Surface => game.noise.biome(player.x() as f64, player.y() as f64).name(),
+        Depth::Caves => game.depth.name(),
+    };
+    let text = format!("nbr of loaded chunks:{} | nbr of modified chunks:{} | x:{} | y:{} | {}", game.loaded_chunks.len(), game.chunk_deltas.len(), player.x(), player.y(), place);
     let paragraph = Paragraph::new(text)
         .block(Block::default().title(TITLE).borders(Borders::ALL));
     frame.render_widget(paragraph, hchunks0[0]);
 
+    // life, hunger, level and effects \\
+    let effects = player.effects().to_spans();
+    let width = effects.iter().map(|span| span.width() as u16).sum::<u16>() + 2;
+    let hchunks2 = Layout::default()
+        .constraints([Constraint::Min(10), Constraint::Min(10), Constraint::Min(10), Constraint::Length(if effects.is_empty() { 0 } else { width })])
+        .direction(tui::layout::Direction::Horizontal)
+        .split(hchunks0[1]);
+
     let lifebar = Gauge::default()
         .block(Block::default().title("[Life]").borders(Borders::ALL))
         .gauge_style(Style::default().fg(Color::Red))
         .ratio(player.life_ratio());
-    frame.render_widget(lifebar, hchunks0[1]);
+    frame.render_widget(lifebar, hchunks2[0]);
+
+    let hungerbar = Gauge::default()
+        .block(Block::default().title("[Hunger]").borders(Borders::ALL))
+        .gauge_style(Style::default().fg(Color::Rgb(145, 77, 5)))
+        .ratio(player.hunger_ratio());
+    frame.render_widget(hungerbar, hchunks2[1]);
+
+    let experience = player.experience();
+    let xpbar = Gauge::default()
+        .block(Block::default().title(format!("[Level {}]", experience.level())).borders(Borders::ALL))
+        .gauge_style(Style::default().fg(Color::LightGreen))
+        .label(experience.label())
+        .ratio(experience.ratio());
+    frame.render_widget(xpbar, hchunks2[2]);
+
+    if !effects.is_empty() {
+        let para_effects = Paragraph::new(Spans::from(effects))
+            .block(Block::default().title("[Effects]").borders(Borders::ALL));
+        frame.render_widget(para_effects, hchunks2[3]);
+    }
 
     let hchunks1 = Layout::default()
         .constraints([Constraint::Min(3), Constraint::Length(4)])
@@ -397,13 +855,13 @@ fn draw<'a, B: Backend>(frame: &mut Frame<B>, game: &mut Game, player: &mut Play
         .block(Block::default().title(TITLE).borders(Borders::ALL))
         .marker(symbols::Marker::Block)
         .paint(|ctx| {
-            for chunk in &game.loaded_chunks {
+            for chunk in game.loaded_chunks.values() {
                 chunk.draw(ctx);
             }
-            player.draw(ctx);
             for entity in &game.entities {
                 entity.draw(ctx)
             }
+            player.draw(ctx);
         });
     frame.render_widget(canvas, hchunks1[0]);
 
@@ -418,3 +876,59 @@ fn draw<'a, B: Backend>(frame: &mut Frame<B>, game: &mut Game, player: &mut Play
         .block(Block::default().title(TITLE).borders(Borders::ALL));
     frame.render_widget(para_message, vchunks[2]);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::snake::SNAKE;
+
+    #[test]
+    fn striking_an_immune_target_deals_nothing() {
+        let mut game = Game::new(42);
+        game.update_chunks();
+        game.add_entity(SNAKE.spawn(5, 5));
+        let id = game.entities()[0].id();
+        assert_eq!(game.strike(id, 1, Direction::Down), Some(1));
+        let life = game.get_entity(id).unwrap().life();
+        assert_eq!(game.strike(id, 1, Direction::Down), None);
+        assert_eq!(game.get_entity(id).unwrap().life(), life);
+    }
+
+    #[test]
+    fn removing_an_entity_keeps_the_others_indexed() {
+        let mut game = Game::new(42);
+        for x in 0..4 {
+            game.add_entity(SNAKE.spawn(x, 7));
+        }
+        let ids: Vec<EntityId> = game.entities().iter().map(|e| e.id()).collect();
+        assert!(game.remove_entity(ids[1]).is_some());
+        assert!(game.get_entity(ids[1]).is_none());
+        assert_eq!(game.get_entity_id(1, 7), None);
+        for (x, &id) in ids.iter().enumerate().filter(|&(x, _)| x != 1) {
+            assert_eq!(game.get_entity(id).map(|e| e.position()), Some((x as i64, 7)));
+            assert_eq!(game.get_entity_id(x as i64, 7), Some(id));
+        }
+    }
+
+    #[test]
+    fn spawn_points_are_free_dry_tiles() {
+        for seed in 0..20 {
+            let mut game = Game::new(seed);
+            let (x, y) = game.spawn_point();
+            assert!(game.is_available(x, y), "seed {seed} spawns on ({x}, {y})");
+        }
+    }
+
+    #[test]
+    fn items_on_the_ground_do_not_block_the_way() {
+        let mut game = Game::new(42);
+        game.update_chunks();
+        let (x, y) = game.spawn_point();
+        game.drop_item(x, y, ItemKind::Stick(crate::items::stick::Stick::new(1)));
+        assert!(game.is_available(x, y));
+        assert_eq!(game.get_creature_id(x, y), None);
+        game.add_entity(SNAKE.spawn(x, y));
+        assert!(!game.is_available(x, y));
+        assert!(game.get_creature_id(x, y).is_some());
+    }
+}
@@ -1,6 +1,6 @@
 use tui::{widgets::ListItem, text::{Span, Spans, Text}, style::{Style, Color}};
 use std::{ops::{Index, IndexMut}, mem::discriminant};
-use crate::items::{ItemKind, axe::Axe, pickaxe::Pickaxe, stone::Stone, stick::Stick, iron::Iron, wood::Wood, hand::Hand, bow::Bow};
+use crate::items::{ItemKind, axe::Axe, pickaxe::Pickaxe, stone::Stone, stick::Stick, iron::Iron, wood::Wood, hand::Hand, bow::Bow, boat::Boat, arrow::Arrow, sword::Sword, armor::Armor, helmet::Helmet, coal::Coal, campfire::Campfire, tier::Tier};
 
 #[derive(Clone)]
 pub enum Direction {
@@ -17,54 +17,64 @@ pub enum Direction {
 
 
 pub enum Recipe {
-    Axe,
-    Pickaxe,
+    Axe(Tier),
+    Pickaxe(Tier),
     Boat,
     Armor,
-    Sword,
+    Helmet,
+    Sword(Tier),
     Bow,
-    Arrow
+    Arrow,
+    Campfire
 }
 
 impl Recipe {
     pub fn get_item(&self) -> ItemKind {
         match self {
-            Recipe::Axe => ItemKind::Axe(Axe::new()),
-            Recipe::Pickaxe => ItemKind::Pickaxe(Pickaxe::new(1)),
-            Recipe::Boat => ItemKind::Axe(Axe::new()),
-            Recipe::Armor => ItemKind::Axe(Axe::new()),
-            Recipe::Arrow => ItemKind::Axe(Axe::new()),
-            Recipe::Sword => ItemKind::Axe(Axe::new()),
+            Recipe::Axe(tier) => ItemKind::Axe(Axe::new(*tier)),
+            Recipe::Pickaxe(tier) => ItemKind::Pickaxe(Pickaxe::new(*tier)),
+            Recipe::Boat => ItemKind::Boat(Boat::new(1)),
+            Recipe::Armor => ItemKind::Armor(Armor::new(1)),
+            Recipe::Helmet => ItemKind::Helmet(Helmet::new(1)),
+            Recipe::Arrow => ItemKind::Arrow(Arrow::new(4)),
+            Recipe::Sword(tier) => ItemKind::Sword(Sword::new(*tier)),
             Recipe::Bow => ItemKind::Bow(Bow::new()),
+            Recipe::Campfire => ItemKind::Campfire(Campfire::new(1)),
         }
     }
 
-    pub fn name<'a>(&self) -> String {
-        let s = match self {
-            Recipe::Pickaxe => "pickaxe",
-            Recipe::Axe => "axe",
-            Recipe::Arrow => "arrow",
-            Recipe::Armor => "armor",
-            Recipe::Bow => "bow",
-            Recipe::Boat => "boat",
-            Recipe::Sword => "sword"
-        };
-        String::from(s)
+    pub fn name(&self) -> String {
+        self.get_item().full_name()
+    }
+
+    /// Experience given to the player for crafting it.
+    pub fn xp(&self) -> u32 {
+        match self {
+            Recipe::Pickaxe(tier) | Recipe::Axe(tier) | Recipe::Sword(tier) => 2 + 3 * *tier as u32,
+            Recipe::Armor => 6,
+            Recipe::Helmet => 4,
+            Recipe::Boat => 4,
+            Recipe::Bow => 3,
+            Recipe::Arrow => 1,
+            Recipe::Campfire => 2,
+        }
     }
 
     pub fn needs(&self) -> Vec<(ItemKind, i8)> {
         match self {
-            Recipe::Pickaxe => vec![(ItemKind::Stone(Stone::new(1)), 5), (ItemKind::Stick(Stick::new(1)), 2)],
-            Recipe::Axe => vec![(ItemKind::Stone(Stone::new(1)), 7), (ItemKind::Stick(Stick::new(1)), 2)],
+            Recipe::Pickaxe(tier) => vec![(tier.material(), 5), (ItemKind::Stick(Stick::new(1)), 2)],
+            Recipe::Axe(tier) => vec![(tier.material(), 7), (ItemKind::Stick(Stick::new(1)), 2)],
             Recipe::Arrow => vec![(ItemKind::Stone(Stone::new(1)), 1), (ItemKind::Stick(Stick::new(1)), 1)],
             Recipe::Armor => vec![(ItemKind::Iron(Iron::new(1)), 20)],
+            Recipe::Helmet => vec![(ItemKind::Iron(Iron::new(1)), 8)],
             Recipe::Boat => vec![(ItemKind::Wood(Wood::new(1)), 20)],
             Recipe::Bow => vec![(ItemKind::Stick(Stick::new(1)), 20)],
-            Recipe::Sword => vec![(ItemKind::Iron(Iron::new(1)), 10)],
+            Recipe::Sword(tier) => vec![(tier.material(), 10), (ItemKind::Stick(Stick::new(1)), 1)],
+            Recipe::Campfire => vec![(ItemKind::Stick(Stick::new(1)), 6), (ItemKind::Coal(Coal::new(1)), 2)],
         }
     }
 
-    pub fn information(&self, inventory: &Inventory) -> Text {
+    pub fn information(&self, inventory: &Inventory) -> Text<'_> {
         let mut text = Vec::new();
         for (item, amount) in self.needs() {
             let total_quantity = inventory.total_quantity(&item);
@@ -82,7 +92,7 @@ impl Recipe {
         Text::from(text)
     }
 
-    pub fn item_list(inventory: &Inventory) -> Vec<ListItem> {
+    pub fn item_list(inventory: &Inventory) -> Vec<ListItem<'_>> {
         Recipe::recipes()
             .iter()
             .map(|x| {
@@ -94,13 +104,21 @@ impl Recipe {
 
     pub fn recipes() -> Vec<Recipe> {
         vec![
-            Recipe::Pickaxe,
-            Recipe::Axe,
+            Recipe::Pickaxe(Tier::Stone),
+            Recipe::Pickaxe(Tier::Iron),
+            Recipe::Pickaxe(Tier::Gold),
+            Recipe::Axe(Tier::Stone),
+            Recipe::Axe(Tier::Iron),
+            Recipe::Axe(Tier::Gold),
+            Recipe::Sword(Tier::Stone),
+            Recipe::Sword(Tier::Iron),
+            Recipe::Sword(Tier::Gold),
             Recipe::Armor,
+            Recipe::Helmet,
             Recipe::Arrow,
             Recipe::Boat,
-            Recipe::Sword,
             Recipe::Bow,
+            Recipe::Campfire,
         ]
     }
 }
@@ -114,6 +132,12 @@ impl Recipe {
 
 pub struct Inventory(Vec<ItemKind>);
 
+impl Default for Inventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Inventory {
     /// Create new empty inventory
     pub fn new() -> Self {
@@ -125,10 +149,10 @@ impl Inventory {
     }
 
     /// add an item to the inventory
-    pub fn add(&mut self, mut item_to_add: ItemKind) {
+    pub fn add(&mut self, item_to_add: ItemKind) {
         let mut quantity = item_to_add.quantity();
         for item in &mut self.0 {
-            if discriminant(item) == discriminant(&mut item_to_add) {
+            if discriminant(item) == discriminant(&item_to_add) {
                 quantity = item.change_quantity(quantity);
                 if quantity == 0 { return }
             }
@@ -139,7 +163,7 @@ impl Inventory {
     pub fn total_quantity(&self, item_type: &ItemKind) -> u32 {
         let mut total: u32 = 0;
         for item in &self.0 {
-            if discriminant(item) == discriminant(&item_type) {
+            if discriminant(item) == discriminant(item_type) {
                 total += item.quantity() as u32;
             }
         }
@@ -156,7 +180,7 @@ impl Inventory {
     }
 
     pub fn craft(&mut self, recipe: &Recipe) -> String {
-        if self.can_craft(&recipe) {
+        if self.can_craft(recipe) {
             for (item_needed, mut amount) in recipe.needs() {
                 for item in &mut self.0 {
                     if discriminant(item) == discriminant(&item_needed) {
@@ -176,7 +200,7 @@ impl Inventory {
         &mut self.0[index]
     }
 
-    pub fn to_item_list(&self) -> Vec<ListItem> {
+    pub fn to_item_list(&self) -> Vec<ListItem<'_>> {
         let mut listitem = Vec::new();
         for item in &self.0 {
             listitem.push(ListItem::new(item.shape()));
@@ -184,24 +208,41 @@ impl Inventory {
         listitem
     }
 
-    pub fn to_extended_item_list(&self) -> Vec<ListItem> {
+    pub fn to_extended_item_list(&self) -> Vec<ListItem<'_>> {
         let mut listitem = Vec::new();
         for item in &self.0 {
-            let spans = Spans::from(vec![
-                Span::from(item.name()),
+            let mut spans = vec![
+                Span::from(item.full_name()),
                 Span::from(" ["),
                 item.shape(),
                 Span::from("] x"),
                 Span::from(item.quantity().to_string()),
-            ]);
+            ];
+            if let Some(tool) = item.as_tool() {
+                spans.push(Span::from(format!(" ({}/{})", tool.durability(), tool.tier().durability())));
+            }
+            let spans = Spans::from(spans);
             listitem.push(ListItem::new(spans));
         }
         listitem
     }
 
+    /// Where the first stack of this kind of item is.
+    pub fn find(&self, item_type: &ItemKind) -> Option<usize> {
+        self.0.iter().position(|item| discriminant(item) == discriminant(item_type))
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn remove(&mut self, index: usize) -> ItemKind {
+        self.0.remove(index)
+    }
 }
 
 impl Index<usize> for Inventory {
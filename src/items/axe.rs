@@ -1,23 +1,27 @@
 use tui::{text::Span, style::{Style, Color}};
-use crate::entities::{EntityKind, swing::Swing};
+use crate::entities::swing::Swing;
 
-use super::Item;
+use super::{Item, tier::{Tier, Tool}};
 
 pub struct Axe {
     quantity: i8,
+    tier: Tier,
+    durability: u16,
 }
 
 impl Axe {
-    pub fn new() -> Self {
+    pub fn new(tier: Tier) -> Self {
         Self {
-            quantity: 1
+            quantity: 1,
+            tier,
+            durability: tier.durability(),
         }
     }
 }
 
 impl Item for Axe {
     fn utilize(&self, coords: (i64, i64, crate::entities::Direction)) -> Option<crate::entities::EntityKind> {
-        Some(EntityKind::Swing(Swing::new(coords.0, coords.1, coords.2, 10)))
+        Some(Box::new(Swing::new(coords.0, coords.1, coords.2, 10 + self.tier.damage_bonus())))
     }
 
     fn shape<'a>() -> tui::text::Span<'a> {
@@ -29,7 +33,7 @@ impl Item for Axe {
     }
 
     fn damage(&self) -> u8 {
-        2
+        2 + self.tier.damage_bonus()
     }
 
     fn quantity(&self) -> i8 {
@@ -44,13 +48,27 @@ impl Item for Axe {
         let prevision = self.quantity + amount;
         if prevision < 0 {
             self.quantity = 0;
-            return prevision;
+            prevision
         } else if prevision > self.max_quantity() {
             self.quantity = self.max_quantity();
-            return self.max_quantity() - prevision;
+            self.max_quantity() - prevision
         } else {
             self.quantity = prevision;
-            return 0;
+            0
         }
     }
 }
+
+impl Tool for Axe {
+    fn tier(&self) -> Tier {
+        self.tier
+    }
+
+    fn durability(&self) -> u16 {
+        self.durability
+    }
+
+    fn set_durability(&mut self, durability: u16) {
+        self.durability = durability;
+    }
+}
@@ -12,6 +12,12 @@ pub struct Bow {
     quantity: i8
 }
 
+impl Default for Bow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Bow {
     pub fn new() -> Bow {
         Bow {
@@ -23,7 +29,7 @@ impl Bow {
 impl Item for Bow {
     fn utilize(&self, coords: (i64, i64, Direction)) -> Option<EntityKind> {
         let (x, y, direction) = coords;
-        Some(EntityKind::Arrow(Arrow::new(x, y, direction, 5)))
+        Some(Box::new(Arrow::new(x, y, direction, 5)))
     }
 
     fn shape<'a>() -> tui::text::Span<'a> {
@@ -50,13 +56,13 @@ impl Item for Bow {
         let prevision = self.quantity + amount;
         if prevision < 0 {
             self.quantity = 0;
-            return prevision;
+            prevision
         } else if prevision > self.max_quantity() {
             self.quantity = self.max_quantity();
-            return self.max_quantity() - prevision;
+            self.max_quantity() - prevision
         } else {
             self.quantity = prevision;
-            return 0;
+            0
         }
     }
 }
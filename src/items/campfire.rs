@@ -0,0 +1,55 @@
+use super::Item;
+use tui::{text::Span, style::{Style, Color}};
+
+/// Carried around and set up in front of the player to cook on.
+pub struct Campfire {
+    quantity: i8,
+}
+
+impl Campfire {
+    pub fn new(quantity: i8) -> Self {
+        Self {
+            quantity
+        }
+    }
+}
+
+impl Item for Campfire {
+    fn utilize(&self, _coords: (i64, i64, crate::entities::Direction)) -> Option<crate::entities::EntityKind> {
+        None
+    }
+
+    fn shape<'a>() -> tui::text::Span<'a> {
+        Span::styled("ʌ", Style::default().fg(Color::Rgb(247, 138, 7)))
+    }
+
+    fn name<'a>() -> &'a str {
+        "campfire"
+    }
+
+    fn damage(&self) -> u8 {
+        1
+    }
+
+    fn quantity(&self) -> i8 {
+        self.quantity
+    }
+
+    fn max_quantity(&self) -> i8 {
+        4
+    }
+
+    fn change_quantity(&mut self, amount: i8) -> i8 {
+        let prevision = self.quantity + amount;
+        if prevision < 0 {
+            self.quantity = 0;
+            prevision
+        } else if prevision > self.max_quantity() {
+            self.quantity = self.max_quantity();
+            self.max_quantity() - prevision
+        } else {
+            self.quantity = prevision;
+            0
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use super::Item;
+use tui::{text::Span, style::{Style, Color}};
+
+pub struct CookedMeat {
+    quantity: i8,
+}
+
+impl CookedMeat {
+    pub fn new(quantity: i8) -> Self {
+        Self {
+            quantity
+        }
+    }
+}
+
+impl Item for CookedMeat {
+    fn utilize(&self, _coords: (i64, i64, crate::entities::Direction)) -> Option<crate::entities::EntityKind> {
+        None
+    }
+
+    fn shape<'a>() -> tui::text::Span<'a> {
+        Span::styled("%", Style::default().fg(Color::Rgb(145, 77, 5)))
+    }
+
+    fn name<'a>() -> &'a str {
+        "cooked meat"
+    }
+
+    fn damage(&self) -> u8 {
+        1
+    }
+
+    fn quantity(&self) -> i8 {
+        self.quantity
+    }
+
+    fn max_quantity(&self) -> i8 {
+        20
+    }
+
+    fn change_quantity(&mut self, amount: i8) -> i8 {
+        let prevision = self.quantity + amount;
+        if prevision < 0 {
+            self.quantity = 0;
+            prevision
+        } else if prevision > self.max_quantity() {
+            self.quantity = self.max_quantity();
+            self.max_quantity() - prevision
+        } else {
+            self.quantity = prevision;
+            0
+        }
+    }
+}
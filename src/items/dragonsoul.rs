@@ -11,10 +11,18 @@ pub struct DragonSoul {
     quantity: i8
 }
 
+impl DragonSoul {
+    pub fn new(quantity: i8) -> Self {
+        Self {
+            quantity
+        }
+    }
+}
+
 impl Item for DragonSoul {
     fn utilize(&self, coords: (i64, i64, Direction)) -> Option<EntityKind> {
         let (x, y, direction) = coords;
-        Some(EntityKind::Fire(Fire::new(x, y, direction)))
+        Some(Box::new(Fire::new(x, y, direction)))
     }
 
     fn shape<'a>() -> tui::text::Span<'a> {
@@ -41,13 +49,13 @@ impl Item for DragonSoul {
         let prevision = self.quantity + amount;
         if prevision < 0 {
             self.quantity = 0;
-            return prevision;
+            prevision
         } else if prevision > self.max_quantity() {
             self.quantity = self.max_quantity();
-            return self.max_quantity() - prevision;
+            self.max_quantity() - prevision
         } else {
             self.quantity = prevision;
-            return 0;
+            0
         }
     }
 }
@@ -23,7 +23,7 @@ impl Item for Gold {
     }
 
     fn name<'a>() -> &'a str {
-        "axe"
+        "gold"
     }
 
     fn damage(&self) -> u8 {
@@ -42,13 +42,13 @@ impl Item for Gold {
         let prevision = self.quantity + amount;
         if prevision < 0 {
             self.quantity = 0;
-            return prevision;
+            prevision
         } else if prevision > self.max_quantity() {
             self.quantity = self.max_quantity();
-            return self.max_quantity() - prevision;
+            self.max_quantity() - prevision
         } else {
             self.quantity = prevision;
-            return 0;
+            0
         }
     }
 }
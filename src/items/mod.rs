@@ -11,14 +11,27 @@ pub mod pickaxe;
 pub mod axe;
 pub mod hand;
 pub mod bow;
+pub mod boat;
+pub mod wool;
+pub mod meat;
+pub mod venom;
+pub mod arrow;
+pub mod sword;
+pub mod armor;
+pub mod helmet;
+pub mod tier;
+pub mod berries;
+pub mod cookedmeat;
+pub mod campfire;
 
-use crate::entities::{EntityKind, Direction};
+use rand::rngs::StdRng;
+use crate::{entities::{EntityKind, Direction}, effects::Effect, equipment::Slot, blocks::{self, Block, BlockKind}};
 use dragonsoul::DragonSoul;
 use onyxheart::OnyxHeart;
 use tui::text::Span;
 use wood::Wood;
 
-use self::{gold::Gold, iron::Iron, coal::Coal, grass::Grass, stick::Stick, stone::Stone, pickaxe::Pickaxe, axe::Axe, hand::Hand, bow::Bow};
+use self::{gold::Gold, iron::Iron, coal::Coal, grass::Grass, stick::Stick, stone::Stone, pickaxe::Pickaxe, axe::Axe, hand::Hand, bow::Bow, boat::Boat, wool::Wool, meat::Meat, venom::Venom, arrow::Arrow, sword::Sword, armor::Armor, helmet::Helmet, tier::{Tier, Tool}, berries::Berries, cookedmeat::CookedMeat, campfire::Campfire};
 
 pub enum ItemKind {
     OH(OnyxHeart),
@@ -33,7 +46,18 @@ pub enum ItemKind {
     Pickaxe(Pickaxe),
     Axe(Axe),
     Hand(Hand),
-    Bow(Bow)
+    Bow(Bow),
+    Boat(Boat),
+    Wool(Wool),
+    Meat(Meat),
+    Venom(Venom),
+    Arrow(Arrow),
+    Sword(Sword),
+    Armor(Armor),
+    Helmet(Helmet),
+    Berries(Berries),
+    CookedMeat(CookedMeat),
+    Campfire(Campfire)
 }
 
 impl ItemKind {
@@ -52,6 +76,117 @@ impl ItemKind {
             ItemKind::Axe(i) => i.utilize(coords),
             ItemKind::Hand(i) => i.utilize(coords),
             ItemKind::Bow(i) => i.utilize(coords),
+            ItemKind::Boat(i) => i.utilize(coords),
+            ItemKind::Wool(i) => i.utilize(coords),
+            ItemKind::Meat(i) => i.utilize(coords),
+            ItemKind::Venom(i) => i.utilize(coords),
+            ItemKind::Arrow(i) => i.utilize(coords),
+            ItemKind::Sword(i) => i.utilize(coords),
+            ItemKind::Armor(i) => i.utilize(coords),
+            ItemKind::Helmet(i) => i.utilize(coords),
+            ItemKind::Berries(i) => i.utilize(coords),
+            ItemKind::CookedMeat(i) => i.utilize(coords),
+            ItemKind::Campfire(i) => i.utilize(coords),
+        }
+    }
+
+    pub fn as_tool(&self) -> Option<&dyn Tool> {
+        match self {
+            ItemKind::Pickaxe(i) => Some(i),
+            ItemKind::Axe(i) => Some(i),
+            ItemKind::Sword(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    pub fn as_tool_mut(&mut self) -> Option<&mut dyn Tool> {
+        match self {
+            ItemKind::Pickaxe(i) => Some(i),
+            ItemKind::Axe(i) => Some(i),
+            ItemKind::Sword(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Use the item once, returns whether it broke. Only tools wear out.
+    pub fn wear(&mut self) -> bool {
+        match self.as_tool_mut() {
+            Some(tool) => {
+                tool.set_durability(tool.durability().saturating_sub(1));
+                tool.durability() == 0
+            },
+            None => false,
+        }
+    }
+
+    /// Name with the material of tools.
+    pub fn full_name(&self) -> String {
+        match self.as_tool() {
+            Some(tool) => format!("{} {}", tool.tier().name(), self.name()),
+            None => self.name().to_string(),
+        }
+    }
+
+    /// Where the item goes when equipped, `None` for what can't be.
+    pub fn slot(&self) -> Option<Slot> {
+        match self {
+            ItemKind::Helmet(_) => Some(Slot::Head),
+            ItemKind::Armor(_) => Some(Slot::Body),
+            // tools stay in the inventory, where mining and digging take them from
+            ItemKind::Sword(_) => Some(Slot::MainHand),
+            ItemKind::Arrow(_) => Some(Slot::Offhand),
+            _ => None,
+        }
+    }
+
+    /// Damage taken off every hit while worn.
+    pub fn defense(&self) -> u8 {
+        match self {
+            ItemKind::Helmet(_) => 2,
+            ItemKind::Armor(_) => 4,
+            _ => 0,
+        }
+    }
+
+    /// What has to be spent each time the item is used.
+    pub fn ammunition(&self) -> Option<ItemKind> {
+        match self {
+            ItemKind::Bow(_) => Some(ItemKind::Arrow(Arrow::new(1))),
+            _ => None,
+        }
+    }
+
+    /// Hunger restored when eaten, `None` for what can't be eaten.
+    pub fn nourishment(&self) -> Option<u8> {
+        match self {
+            ItemKind::Berries(_) => Some(6),
+            ItemKind::Meat(_) => Some(10),
+            ItemKind::CookedMeat(_) => Some(30),
+            _ => None,
+        }
+    }
+
+    /// Effect given when eaten and for how many ticks.
+    pub fn effect(&self) -> Option<(Effect, u16)> {
+        match self {
+            ItemKind::CookedMeat(_) => Some((Effect::Regeneration, 100)),
+            _ => None,
+        }
+    }
+
+    /// Builds the block the item is set up as in front of the player, `None` for what can't be.
+    pub fn placed(&self) -> Option<fn(&mut StdRng) -> BlockKind> {
+        match self {
+            ItemKind::Campfire(_) => Some(blocks::campfire::Campfire::generate),
+            _ => None,
+        }
+    }
+
+    /// What the item turns into over a campfire.
+    pub fn cooked(&self) -> Option<ItemKind> {
+        match self {
+            ItemKind::Meat(_) => Some(ItemKind::CookedMeat(CookedMeat::new(1))),
+            _ => None,
         }
     }
 
@@ -70,6 +205,17 @@ impl ItemKind {
             ItemKind::Axe(i) => i.damage(),
             ItemKind::Hand(i) => i.damage(),
             ItemKind::Bow(i) => i.damage(),
+            ItemKind::Boat(i) => i.damage(),
+            ItemKind::Wool(i) => i.damage(),
+            ItemKind::Meat(i) => i.damage(),
+            ItemKind::Venom(i) => i.damage(),
+            ItemKind::Arrow(i) => i.damage(),
+            ItemKind::Sword(i) => i.damage(),
+            ItemKind::Armor(i) => i.damage(),
+            ItemKind::Helmet(i) => i.damage(),
+            ItemKind::Berries(i) => i.damage(),
+            ItemKind::CookedMeat(i) => i.damage(),
+            ItemKind::Campfire(i) => i.damage(),
         }
     }
 
@@ -88,10 +234,21 @@ impl ItemKind {
             ItemKind::Axe(_) => Axe::shape(),
             ItemKind::Hand(_) => Hand::shape(),
             ItemKind::Bow(_) => Bow::shape(),
+            ItemKind::Boat(_) => Boat::shape(),
+            ItemKind::Wool(_) => Wool::shape(),
+            ItemKind::Meat(_) => Meat::shape(),
+            ItemKind::Venom(_) => Venom::shape(),
+            ItemKind::Arrow(_) => Arrow::shape(),
+            ItemKind::Sword(_) => Sword::shape(),
+            ItemKind::Armor(_) => Armor::shape(),
+            ItemKind::Helmet(_) => Helmet::shape(),
+            ItemKind::Berries(_) => Berries::shape(),
+            ItemKind::CookedMeat(_) => CookedMeat::shape(),
+            ItemKind::Campfire(_) => Campfire::shape(),
         }
     }
 
-    pub fn name<'a>(&self) -> &str {
+    pub fn name(&self) -> &str {
         match self {
             ItemKind::OH(_) => OnyxHeart::name(),
             ItemKind::DS(_) => DragonSoul::name(),
@@ -106,6 +263,17 @@ impl ItemKind {
             ItemKind::Axe(_) => Axe::name(),
             ItemKind::Hand(_) => Hand::name(),
             ItemKind::Bow(_) => Bow::name(),
+            ItemKind::Boat(_) => Boat::name(),
+            ItemKind::Wool(_) => Wool::name(),
+            ItemKind::Meat(_) => Meat::name(),
+            ItemKind::Venom(_) => Venom::name(),
+            ItemKind::Arrow(_) => Arrow::name(),
+            ItemKind::Sword(_) => Sword::name(),
+            ItemKind::Armor(_) => Armor::name(),
+            ItemKind::Helmet(_) => Helmet::name(),
+            ItemKind::Berries(_) => Berries::name(),
+            ItemKind::CookedMeat(_) => CookedMeat::name(),
+            ItemKind::Campfire(_) => Campfire::name(),
         }
     }
 
@@ -124,6 +292,17 @@ impl ItemKind {
             ItemKind::Axe(i) => i.quantity(),
             ItemKind::Hand(i) => i.quantity(),
             ItemKind::Bow(i) => i.quantity(),
+            ItemKind::Boat(i) => i.quantity(),
+            ItemKind::Wool(i) => i.quantity(),
+            ItemKind::Meat(i) => i.quantity(),
+            ItemKind::Venom(i) => i.quantity(),
+            ItemKind::Arrow(i) => i.quantity(),
+            ItemKind::Sword(i) => i.quantity(),
+            ItemKind::Armor(i) => i.quantity(),
+            ItemKind::Helmet(i) => i.quantity(),
+            ItemKind::Berries(i) => i.quantity(),
+            ItemKind::CookedMeat(i) => i.quantity(),
+            ItemKind::Campfire(i) => i.quantity(),
         }
     }
     pub fn change_quantity(&mut self, amount: i8) -> i8 {
@@ -141,8 +320,98 @@ impl ItemKind {
             ItemKind::Axe(i) => i.change_quantity(amount),
             ItemKind::Hand(i) => i.change_quantity(amount),
             ItemKind::Bow(i) => i.change_quantity(amount),
+            ItemKind::Boat(i) => i.change_quantity(amount),
+            ItemKind::Wool(i) => i.change_quantity(amount),
+            ItemKind::Meat(i) => i.change_quantity(amount),
+            ItemKind::Venom(i) => i.change_quantity(amount),
+            ItemKind::Arrow(i) => i.change_quantity(amount),
+            ItemKind::Sword(i) => i.change_quantity(amount),
+            ItemKind::Armor(i) => i.change_quantity(amount),
+            ItemKind::Helmet(i) => i.change_quantity(amount),
+            ItemKind::Berries(i) => i.change_quantity(amount),
+            ItemKind::CookedMeat(i) => i.change_quantity(amount),
+            ItemKind::Campfire(i) => i.change_quantity(amount),
         }
     }
+
+    /// Name of the item in save and data files.
+    pub fn key(&self) -> &'static str {
+        match self {
+            ItemKind::OH(_) => "onyx_heart",
+            ItemKind::DS(_) => "dragon_soul",
+            ItemKind::Wood(_) => "wood",
+            ItemKind::Gold(_) => "gold",
+            ItemKind::Iron(_) => "iron",
+            ItemKind::Stone(_) => "stone",
+            ItemKind::Coal(_) => "coal",
+            ItemKind::Grass(_) => "grass",
+            ItemKind::Stick(_) => "stick",
+            ItemKind::Pickaxe(i) => match i.tier() {
+                Tier::Stone => "stone_pickaxe",
+                Tier::Iron => "iron_pickaxe",
+                Tier::Gold => "gold_pickaxe",
+            },
+            ItemKind::Axe(i) => match i.tier() {
+                Tier::Stone => "stone_axe",
+                Tier::Iron => "iron_axe",
+                Tier::Gold => "gold_axe",
+            },
+            ItemKind::Hand(_) => "hand",
+            ItemKind::Bow(_) => "bow",
+            ItemKind::Boat(_) => "boat",
+            ItemKind::Wool(_) => "wool",
+            ItemKind::Meat(_) => "meat",
+            ItemKind::Venom(_) => "venom",
+            ItemKind::Arrow(_) => "arrow",
+            ItemKind::Sword(i) => match i.tier() {
+                Tier::Stone => "stone_sword",
+                Tier::Iron => "iron_sword",
+                Tier::Gold => "gold_sword",
+            },
+            ItemKind::Armor(_) => "armor",
+            ItemKind::Helmet(_) => "helmet",
+            ItemKind::Berries(_) => "berries",
+            ItemKind::CookedMeat(_) => "cooked_meat",
+            ItemKind::Campfire(_) => "campfire",
+        }
+    }
+
+    pub fn from_key(key: &str, quantity: i8) -> Option<Self> {
+        Some(match key {
+            "onyx_heart" => ItemKind::OH(OnyxHeart::new(quantity)),
+            "dragon_soul" => ItemKind::DS(DragonSoul::new(quantity)),
+            "wood" => ItemKind::Wood(Wood::new(quantity)),
+            "gold" => ItemKind::Gold(Gold::new(quantity)),
+            "iron" => ItemKind::Iron(Iron::new(quantity)),
+            "stone" => ItemKind::Stone(Stone::new(quantity)),
+            "coal" => ItemKind::Coal(Coal::new(quantity)),
+            "grass" => ItemKind::Grass(Grass::new(quantity)),
+            "stick" => ItemKind::Stick(Stick::new(quantity)),
+            // tools saved before they had tiers are made of stone
+            "pickaxe" | "stone_pickaxe" => ItemKind::Pickaxe(Pickaxe::new(Tier::Stone)),
+            "iron_pickaxe" => ItemKind::Pickaxe(Pickaxe::new(Tier::Iron)),
+            "gold_pickaxe" => ItemKind::Pickaxe(Pickaxe::new(Tier::Gold)),
+            "axe" | "stone_axe" => ItemKind::Axe(Axe::new(Tier::Stone)),
+            "iron_axe" => ItemKind::Axe(Axe::new(Tier::Iron)),
+            "gold_axe" => ItemKind::Axe(Axe::new(Tier::Gold)),
+            "hand" => ItemKind::Hand(Hand::new(quantity)),
+            "bow" => ItemKind::Bow(Bow::new()),
+            "boat" => ItemKind::Boat(Boat::new(quantity)),
+            "wool" => ItemKind::Wool(Wool::new(quantity)),
+            "meat" => ItemKind::Meat(Meat::new(quantity)),
+            "venom" => ItemKind::Venom(Venom::new(quantity)),
+            "arrow" => ItemKind::Arrow(Arrow::new(quantity)),
+            "sword" | "stone_sword" => ItemKind::Sword(Sword::new(Tier::Stone)),
+            "iron_sword" => ItemKind::Sword(Sword::new(Tier::Iron)),
+            "gold_sword" => ItemKind::Sword(Sword::new(Tier::Gold)),
+            "armor" => ItemKind::Armor(Armor::new(quantity)),
+            "helmet" => ItemKind::Helmet(Helmet::new(quantity)),
+            "berries" => ItemKind::Berries(Berries::new(quantity)),
+            "cooked_meat" => ItemKind::CookedMeat(CookedMeat::new(quantity)),
+            "campfire" => ItemKind::Campfire(Campfire::new(quantity)),
+            _ => return None,
+        })
+    }
 }
 
 pub trait Item {
@@ -11,10 +11,18 @@ pub struct OnyxHeart {
     quantity: i8
 }
 
+impl OnyxHeart {
+    pub fn new(quantity: i8) -> Self {
+        Self {
+            quantity
+        }
+    }
+}
+
 impl Item for OnyxHeart {
     fn utilize(&self, coords: (i64, i64, Direction)) -> Option<EntityKind> {
         let (x, y, direction) = coords;
-        Some(EntityKind::OnyxStone(OnyxStone::new(x, y, direction)))
+        Some(Box::new(OnyxStone::new(x, y, direction)))
     }
 
     fn shape<'a>() -> tui::text::Span<'a> {
@@ -41,13 +49,13 @@ impl Item for OnyxHeart {
         let prevision = self.quantity + amount;
         if prevision < 0 {
             self.quantity = 0;
-            return prevision;
+            prevision
         } else if prevision > self.max_quantity() {
             self.quantity = self.max_quantity();
-            return self.max_quantity() - prevision;
+            self.max_quantity() - prevision
         } else {
             self.quantity = prevision;
-            return 0;
+            0
         }
     }
 }
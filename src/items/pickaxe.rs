@@ -1,22 +1,26 @@
-use super::Item;
-use crate::entities::{EntityKind, swing::Swing};
+use super::{Item, tier::{Tier, Tool}};
+use crate::entities::swing::Swing;
 use tui::{text::Span, style::{Style, Color}};
 
 pub struct Pickaxe {
     quantity: i8,
+    tier: Tier,
+    durability: u16,
 }
 
 impl Pickaxe {
-    pub fn new(quantity: i8) -> Self {
+    pub fn new(tier: Tier) -> Self {
         Self {
-            quantity
+            quantity: 1,
+            tier,
+            durability: tier.durability(),
         }
     }
 }
 
 impl Item for Pickaxe {
     fn utilize(&self, coords: (i64, i64, crate::entities::Direction)) -> Option<crate::entities::EntityKind> {
-        Some(EntityKind::Swing(Swing::new(coords.0, coords.1, coords.2, 10)))
+        Some(Box::new(Swing::new(coords.0, coords.1, coords.2, 10 + self.tier.damage_bonus())))
     }
 
     fn shape<'a>() -> tui::text::Span<'a> {
@@ -24,11 +28,11 @@ impl Item for Pickaxe {
     }
 
     fn name<'a>() -> &'a str {
-        "axe"
+        "pickaxe"
     }
 
     fn damage(&self) -> u8 {
-        2
+        2 + self.tier.damage_bonus()
     }
 
     fn quantity(&self) -> i8 {
@@ -43,13 +47,27 @@ impl Item for Pickaxe {
         let prevision = self.quantity + amount;
         if prevision < 0 {
             self.quantity = 0;
-            return prevision;
+            prevision
         } else if prevision > self.max_quantity() {
             self.quantity = self.max_quantity();
-            return self.max_quantity() - prevision;
+            self.max_quantity() - prevision
         } else {
             self.quantity = prevision;
-            return 0;
+            0
         }
     }
 }
+
+impl Tool for Pickaxe {
+    fn tier(&self) -> Tier {
+        self.tier
+    }
+
+    fn durability(&self) -> u16 {
+        self.durability
+    }
+
+    fn set_durability(&mut self, durability: u16) {
+        self.durability = durability;
+    }
+}
@@ -1,5 +1,5 @@
 use super::Item;
-use crate::entities::{EntityKind, swing::Swing};
+use crate::entities::swing::Swing;
 use tui::{text::Span, style::{Style, Color}};
 
 pub struct Stick {
@@ -16,7 +16,7 @@ impl Stick {
 
 impl Item for Stick {
     fn utilize(&self, coords: (i64, i64, crate::entities::Direction)) -> Option<crate::entities::EntityKind> {
-        Some(EntityKind::Swing(Swing::new(coords.0, coords.1, coords.2, 10)))
+        Some(Box::new(Swing::new(coords.0, coords.1, coords.2, 10)))
     }
 
     fn shape<'a>() -> tui::text::Span<'a> {
@@ -44,13 +44,13 @@ impl Item for Stick {
         let prevision = self.quantity + amount;
         if prevision < 0 {
             self.quantity = 0;
-            return prevision;
+            prevision
         } else if prevision > self.max_quantity() {
             self.quantity = self.max_quantity();
-            return self.max_quantity() - prevision;
+            self.max_quantity() - prevision
         } else {
             self.quantity = prevision;
-            return 0;
+            0
         }
     }
 }
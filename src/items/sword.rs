@@ -0,0 +1,73 @@
+use super::{Item, tier::{Tier, Tool}};
+use crate::entities::swing::Swing;
+use tui::{text::Span, style::{Style, Color}};
+
+pub struct Sword {
+    quantity: i8,
+    tier: Tier,
+    durability: u16,
+}
+
+impl Sword {
+    pub fn new(tier: Tier) -> Self {
+        Self {
+            quantity: 1,
+            tier,
+            durability: tier.durability(),
+        }
+    }
+}
+
+impl Item for Sword {
+    fn utilize(&self, coords: (i64, i64, crate::entities::Direction)) -> Option<crate::entities::EntityKind> {
+        Some(Box::new(Swing::new(coords.0, coords.1, coords.2, 20 + self.tier.damage_bonus())))
+    }
+
+    fn shape<'a>() -> tui::text::Span<'a> {
+        Span::styled("†", Style::default().fg(Color::White))
+    }
+
+    fn name<'a>() -> &'a str {
+        "sword"
+    }
+
+    fn damage(&self) -> u8 {
+        8 + self.tier.damage_bonus()
+    }
+
+    fn quantity(&self) -> i8 {
+        self.quantity
+    }
+
+    fn max_quantity(&self) -> i8 {
+        1
+    }
+
+    fn change_quantity(&mut self, amount: i8) -> i8 {
+        let prevision = self.quantity + amount;
+        if prevision < 0 {
+            self.quantity = 0;
+            prevision
+        } else if prevision > self.max_quantity() {
+            self.quantity = self.max_quantity();
+            self.max_quantity() - prevision
+        } else {
+            self.quantity = prevision;
+            0
+        }
+    }
+}
+
+impl Tool for Sword {
+    fn tier(&self) -> Tier {
+        self.tier
+    }
+
+    fn durability(&self) -> u16 {
+        self.durability
+    }
+
+    fn set_durability(&mut self, durability: u16) {
+        self.durability = durability;
+    }
+}
@@ -0,0 +1,54 @@
+use super::{ItemKind, stone::Stone, iron::Iron, gold::Gold};
+
+/// Material a tool is made of, better ones last longer, hit harder and get through harder blocks.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Tier {
+    Stone,
+    Iron,
+    Gold,
+}
+
+impl Tier {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tier::Stone => "stone",
+            Tier::Iron => "iron",
+            Tier::Gold => "gold",
+        }
+    }
+
+    /// Uses before a new tool of this tier breaks.
+    pub fn durability(&self) -> u16 {
+        match self {
+            Tier::Stone => 60,
+            Tier::Iron => 150,
+            Tier::Gold => 300,
+        }
+    }
+
+    /// Damage added to what the tool deals.
+    pub fn damage_bonus(&self) -> u8 {
+        match self {
+            Tier::Stone => 0,
+            Tier::Iron => 2,
+            Tier::Gold => 4,
+        }
+    }
+
+    /// What tools of this tier are crafted from.
+    pub fn material(&self) -> ItemKind {
+        match self {
+            Tier::Stone => ItemKind::Stone(Stone::new(1)),
+            Tier::Iron => ItemKind::Iron(Iron::new(1)),
+            Tier::Gold => ItemKind::Gold(Gold::new(1)),
+        }
+    }
+}
+
+/// Items made of a material which wear out as they are used.
+pub trait Tool {
+    fn tier(&self) -> Tier;
+    /// Uses left before breaking.
+    fn durability(&self) -> u16;
+    fn set_durability(&mut self, durability: u16);
+}
@@ -0,0 +1,36 @@
+use std::collections::{HashMap, HashSet};
+use crate::{chunk::{ChunkDelta, Terrain}, entities::EntityKind};
+
+/// The levels of the world, the player can only be on one of them at a time.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Depth {
+    Surface,
+    Caves,
+}
+
+impl Depth {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Depth::Surface => "surface",
+            Depth::Caves => "caves",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "surface" => Some(Depth::Surface),
+            "caves" => Some(Depth::Caves),
+            _ => None,
+        }
+    }
+}
+
+/// What is kept of a level while the player is on another one.
+/// Its chunks are all unloaded so only their changes remain.
+#[derive(Default)]
+pub struct Layer {
+    pub chunk_deltas: HashMap<(i64, i64), ChunkDelta>,
+    pub explored: HashMap<(i64, i64), Terrain>,
+    pub entities: Vec<EntityKind>,
+    pub populated: HashSet<(i64, i64)>,
+}
@@ -4,4 +4,16 @@ pub mod inventory;
 pub mod game;
 pub mod blocks;
 pub mod chunk;
+pub mod biome;
+pub mod layer;
+pub mod structure;
+pub mod river;
+pub mod spatial;
+pub mod pathfinding;
+pub mod loot;
+pub mod effects;
+pub mod equipment;
+pub mod rules;
+pub mod experience;
+pub mod save;
 pub mod ui;
@@ -0,0 +1,99 @@
+use std::{ops::RangeInclusive, sync::OnceLock};
+use rand::Rng;
+use crate::items::ItemKind;
+
+const LOOT: &str = include_str!("../assets/loot.txt");
+/// Drop standing for an empty roll in the data file.
+const NOTHING: &str = "nothing";
+
+struct LootEntry {
+    item: String,
+    weight: u32,
+    quantity: RangeInclusive<i8>,
+}
+
+/// What a creature may leave behind when killed, as described in the loot file.
+pub struct LootTable {
+    creature: String,
+    entries: Vec<LootEntry>,
+}
+
+impl LootTable {
+    /// Every loot table of the data file, parsed on first use.
+    pub fn all() -> &'static [LootTable] {
+        static ALL: OnceLock<Vec<LootTable>> = OnceLock::new();
+        ALL.get_or_init(|| LootTable::parse(LOOT))
+    }
+
+    /// Loot table of the creature with the given name.
+    pub fn of(creature: &str) -> Option<&'static LootTable> {
+        LootTable::all().iter().find(|table| table.creature == creature)
+    }
+
+    fn parse(data: &str) -> Vec<LootTable> {
+        let mut tables: Vec<LootTable> = Vec::new();
+        for line in data.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(creature) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                tables.push(LootTable { creature: creature.to_string(), entries: Vec::new() });
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let ([item, weight, min, max], Some(table)) = (fields.as_slice(), tables.last_mut()) else {
+                panic!("invalid loot line '{line}'");
+            };
+            if *item != NOTHING && ItemKind::from_key(item, 1).is_none() {
+                panic!("unknown item '{item}'");
+            }
+            let number = |field: &str| -> i64 { field.parse().unwrap_or_else(|_| panic!("invalid number '{field}'")) };
+            table.entries.push(LootEntry {
+                item: item.to_string(),
+                weight: number(weight) as u32,
+                quantity: number(min) as i8..=number(max) as i8,
+            });
+        }
+        tables
+    }
+
+    /// Pick one of the drops, `None` when nothing is left behind.
+    pub fn roll(&self, rng: &mut impl Rng) -> Option<ItemKind> {
+        let total: u32 = self.entries.iter().map(|entry| entry.weight).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut pick = rng.gen_range(0..total);
+        let entry = self.entries.iter().find(|entry| {
+            if pick < entry.weight {
+                true
+            } else {
+                pick -= entry.weight;
+                false
+            }
+        })?;
+        let quantity = rng.gen_range(entry.quantity.clone());
+        ItemKind::from_key(&entry.item, quantity).filter(|_| quantity > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{ovis::OVIS, snake::SNAKE, scorpy::SCORPY, golem::GOLEM, crawler::CRAWLER};
+
+    #[test]
+    fn shipped_loot_tables_parse_and_can_be_rolled() {
+        let tables = LootTable::all();
+        assert!(!tables.is_empty());
+        let creatures = [&OVIS, &SNAKE, &SCORPY, &GOLEM, &CRAWLER].map(|species| species.spawn(0, 0).name());
+        for table in tables {
+            assert!(creatures.contains(&table.creature.as_str()), "no creature is named '{}'", table.creature);
+            assert!(table.entries.iter().any(|entry| entry.weight > 0), "{} can never drop anything", table.creature);
+            for entry in &table.entries {
+                assert!(!entry.quantity.is_empty(), "{} drops {} in an empty range", table.creature, entry.item);
+            }
+        }
+    }
+}
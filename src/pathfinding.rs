@@ -0,0 +1,128 @@
+use std::{
+    cell::{Cell, RefCell},
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+use crate::game::Game;
+
+/// Tiles a search looks at before deciding the goal can't be reached.
+const MAX_NODES: usize = 400;
+
+fn neighbours((x, y): (i64, i64)) -> [(i64, i64); 4] {
+    [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+}
+
+fn distance(a: (i64, i64), b: (i64, i64)) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+/// Shortest way from `from` to `goal` over available tiles, the goal itself
+/// may be taken. The start is not part of the path, `None` when the goal
+/// can't be reached within a few hundred tiles.
+pub fn find_path(game: &Game, from: (i64, i64), goal: (i64, i64)) -> Option<Vec<(i64, i64)>> {
+    let mut open = BinaryHeap::from([Reverse((distance(from, goal), 0, from))]);
+    let mut came_from: HashMap<(i64, i64), (i64, i64)> = HashMap::new();
+    let mut cost = HashMap::from([(from, 0)]);
+    let mut visited = 0;
+    while let Some(Reverse((_, steps, tile))) = open.pop() {
+        if tile == goal {
+            let mut path = vec![tile];
+            while let Some(&previous) = came_from.get(path.last()?) {
+                path.push(previous);
+            }
+            path.pop();
+            path.reverse();
+            return Some(path);
+        }
+        if steps > cost[&tile] {
+            continue;
+        }
+        visited += 1;
+        if visited > MAX_NODES {
+            return None;
+        }
+        for next in neighbours(tile) {
+            if next != goal && !game.is_available(next.0, next.1) {
+                continue;
+            }
+            if cost.get(&next).is_none_or(|&known| steps + 1 < known) {
+                cost.insert(next, steps + 1);
+                came_from.insert(next, tile);
+                open.push(Reverse((steps + 1 + distance(next, goal), steps + 1, next)));
+            }
+        }
+    }
+    None
+}
+
+/// A path being followed, kept between steps so that it is only searched
+/// again when the way gets blocked or the goal wanders off.
+#[derive(Default)]
+pub struct PathCache {
+    path: RefCell<VecDeque<(i64, i64)>>,
+    goal: Cell<Option<(i64, i64)>>,
+}
+
+impl PathCache {
+    /// Next tile to step on to go from `from` to `goal`, `None` if it can't be reached.
+    pub fn next_step(&self, game: &Game, from: (i64, i64), goal: (i64, i64)) -> Option<(i64, i64)> {
+        let mut path = self.path.borrow_mut();
+        // a goal moving next to the end of the path only makes it longer
+        if self.goal.get() != Some(goal) && path.back().is_some_and(|&end| distance(end, goal) == 1) {
+            path.push_back(goal);
+            self.goal.set(Some(goal));
+        }
+        let valid = self.goal.get() == Some(goal) && path.front().is_some_and(|&next| {
+            distance(from, next) == 1 && (next == goal || game.is_available(next.0, next.1))
+        });
+        if !valid {
+            self.goal.set(Some(goal));
+            *path = find_path(game, from, goal).unwrap_or_default().into();
+        }
+        path.pop_front()
+    }
+
+    pub fn clear(&self) {
+        self.path.borrow_mut().clear();
+        self.goal.set(None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::snake::SNAKE;
+
+    #[test]
+    fn paths_step_over_free_tiles_to_the_goal() {
+        let mut game = Game::new(3);
+        let from = game.spawn_point();
+        let mut found = 0;
+        for dx in -6..=6 {
+            let goal = (from.0 + dx, from.1 + 6 - dx.abs());
+            let Some(path) = find_path(&game, from, goal) else { continue };
+            found += 1;
+            assert_eq!(path.last(), Some(&goal));
+            assert!(path.len() as i64 >= distance(from, goal));
+            let mut previous = from;
+            for &tile in &path {
+                assert_eq!(distance(previous, tile), 1);
+                assert!(tile == goal || game.is_available(tile.0, tile.1));
+                previous = tile;
+            }
+        }
+        assert!(found > 0);
+    }
+
+    #[test]
+    fn walled_in_goals_cant_be_reached() {
+        let mut game = Game::new(3);
+        let from = game.spawn_point();
+        let goal = (from.0 + 6, from.1);
+        for (x, y) in neighbours(goal) {
+            game.add_entity(SNAKE.spawn(x, y));
+        }
+        assert_eq!(find_path(&game, from, goal), None);
+        assert_eq!(PathCache::default().next_step(&game, from, goal), None);
+    }
+}
@@ -0,0 +1,167 @@
+use std::{cell::RefCell, collections::HashMap};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use crate::{
+    biome::{Biome, WorldNoise},
+    chunk::{Chunk, Terrain, CHUNK_SIZE, mix},
+};
+
+/// Each square region of this many tiles may hold the spring of one river.
+const RIVER_REGION: i64 = 32;
+/// Chance in percent for a region to have a spring.
+const RIVER_CHANCE: u32 = 70;
+/// Springs only rise from high ground.
+const SPRING_HEIGHT: f64 = 12.0;
+/// Rivers reaching deep water end there, the others stop after this many tiles.
+const RIVER_LENGTH: i64 = 96;
+/// Rivers cut through the rim of hollows up to this many tiles wide,
+/// the others end in a lake of `LAKE_RADIUS`.
+const BREACH_DISTANCE: i64 = 8;
+const LAKE_RADIUS: i64 = 3;
+/// Regions kept around the ones a chunk needs, enough for the chunks in view.
+const CACHE_MARGIN: i64 = 4;
+/// Keeps rivers independent from the other generators using the seed.
+const RIVER_SALT: u32 = 0x27d4eb2d;
+
+/// Rivers already traced, by region. Every chunk within reach of a spring
+/// carves its river, so it is only traced the first time. Regions far from
+/// the last chunk built are forgotten so it does not grow as the player travels.
+#[derive(Default)]
+pub struct RiverCache(RefCell<HashMap<(i64, i64), Option<River>>>);
+
+/// Tiles a river flows through, from its spring downhill.
+struct River {
+    path: Vec<(i64, i64)>,
+    lakes: Vec<(i64, i64)>,
+}
+
+impl River {
+    /// The river springing in the region, it only depends on the seed
+    /// so that every chunk it flows through finds the same one.
+    fn of_region(region: (i64, i64), noise: &WorldNoise, seed: u32) -> Option<Self> {
+        let mut rng = StdRng::seed_from_u64(mix(seed ^ RIVER_SALT, region.0, region.1));
+        if !rng.gen_ratio(RIVER_CHANCE, 100) {
+            return None;
+        }
+        let x = region.0 * RIVER_REGION + rng.gen_range(0..RIVER_REGION);
+        let y = region.1 * RIVER_REGION + rng.gen_range(0..RIVER_REGION);
+        let height = |x: i64, y: i64| noise.height.get_noise(x as f64, y as f64);
+        if height(x, y) < SPRING_HEIGHT {
+            return None;
+        }
+
+        let mut path = vec![(x, y)];
+        let mut lakes = Vec::new();
+        let (mut x, mut y, mut level) = (x, y, height(x, y));
+        while (path.len() as i64) < RIVER_LENGTH && level >= -25.0 {
+            // flow to the lowest neighbour
+            let (next_level, next) = [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .map(|(i, j)| (height(i, j), (i, j)))
+                .into_iter()
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+                .unwrap_or((level, (x, y)));
+            if next_level < level {
+                (x, y, level) = (next.0, next.1, next_level);
+                path.push(next);
+                continue;
+            }
+            // stuck in a hollow, cut through its rim towards lower ground
+            let Some((low_level, low)) = River::breach(x, y, level, &height) else {
+                lakes.push((x, y));
+                break;
+            };
+            while (x, y) != low {
+                if x != low.0 {
+                    x += (low.0 - x).signum();
+                } else {
+                    y += (low.1 - y).signum();
+                }
+                path.push((x, y));
+            }
+            level = low_level;
+        }
+        Some(Self { path, lakes })
+    }
+
+    /// Lowest tile lower than `level` on the closest ring around (x, y) holding one.
+    fn breach(x: i64, y: i64, level: f64, height: &impl Fn(i64, i64) -> f64) -> Option<(f64, (i64, i64))> {
+        (2..=BREACH_DISTANCE).find_map(|radius| {
+            (-radius..=radius)
+                .flat_map(|i| (-radius..=radius).map(move |j| (i, j)))
+                .filter(|(i, j)| i.abs() == radius || j.abs() == radius)
+                .map(|(i, j)| (height(x + i, y + j), (x + i, y + j)))
+                .filter(|(h, _)| *h < level)
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+        })
+    }
+
+    /// Rivers widen as they flow.
+    fn width(step: usize) -> i64 {
+        1 + step as i64 / 40
+    }
+
+    fn carve(&self, chunk: &mut Chunk, noise: &WorldNoise) {
+        let x0 = chunk.0 * CHUNK_SIZE;
+        let y0 = chunk.1 * CHUNK_SIZE;
+        let mut wet = |x: i64, y: i64, radius: i64| {
+            for i in (x - radius - 1).max(x0)..=(x + radius + 1).min(x0 + CHUNK_SIZE - 1) {
+                for j in (y - radius - 1).max(y0)..=(y + radius + 1).min(y0 + CHUNK_SIZE - 1) {
+                    let tile = &mut chunk[((i - x0) as usize, (j - y0) as usize)];
+                    let distance = (i - x).abs().max((j - y).abs());
+                    if matches!(tile.0, Terrain::Water | Terrain::DeepWater | Terrain::Ice) {
+                        continue;
+                    } else if distance <= radius {
+                        // water freezes in the cold like lakes do
+                        let frozen = noise.biome(i as f64, j as f64) == Biome::Tundra;
+                        *tile = (if frozen { Terrain::Ice } else { Terrain::Water }, None);
+                    } else {
+                        tile.0 = Terrain::Sand;
+                    }
+                }
+            }
+        };
+        for (step, &(x, y)) in self.path.iter().enumerate() {
+            wet(x, y, Self::width(step) / 2);
+        }
+        for &(x, y) in &self.lakes {
+            wet(x, y, LAKE_RADIUS);
+        }
+    }
+}
+
+/// Carve the part of every river flowing through the chunk.
+pub fn carve_rivers(chunk: &mut Chunk, noise: &WorldNoise, seed: u32) {
+    // a river never gets further from its spring than its length, cuts through rims included
+    let reach = RIVER_LENGTH + 2 * BREACH_DISTANCE + LAKE_RADIUS + 1;
+    let x0 = chunk.0 * CHUNK_SIZE;
+    let y0 = chunk.1 * CHUNK_SIZE;
+    let regions_x = (x0 - reach).div_euclid(RIVER_REGION)..=(x0 + CHUNK_SIZE + reach).div_euclid(RIVER_REGION);
+    let regions_y = (y0 - reach).div_euclid(RIVER_REGION)..=(y0 + CHUNK_SIZE + reach).div_euclid(RIVER_REGION);
+    let mut rivers = noise.rivers.0.borrow_mut();
+    let kept_x = regions_x.start() - CACHE_MARGIN..=regions_x.end() + CACHE_MARGIN;
+    let kept_y = regions_y.start() - CACHE_MARGIN..=regions_y.end() + CACHE_MARGIN;
+    rivers.retain(|(rx, ry), _| kept_x.contains(rx) && kept_y.contains(ry));
+    for rx in regions_x {
+        for ry in regions_y.clone() {
+            let river = rivers.entry((rx, ry)).or_insert_with(|| River::of_region((rx, ry), noise, seed));
+            if let Some(river) = river {
+                river.carve(chunk, noise);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn river_cache_stays_bounded_while_travelling() {
+        let noise = WorldNoise::new(42);
+        let span = (CHUNK_SIZE + 2 * (RIVER_LENGTH + 2 * BREACH_DISTANCE + LAKE_RADIUS + 1)) / RIVER_REGION + 2;
+        let bound = (span + 2 * CACHE_MARGIN).pow(2) as usize;
+        for col in 0..200 {
+            Chunk::new(col, 0, &noise, 42);
+            assert!(noise.rivers.0.borrow().len() <= bound);
+        }
+    }
+}
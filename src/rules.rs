@@ -0,0 +1,46 @@
+/// What becomes of the inventory when the player dies.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DeathRule {
+    Keep,
+    #[default]
+    Grave,
+    LoseAll,
+}
+
+impl DeathRule {
+    pub const ALL: [DeathRule; 3] = [DeathRule::Keep, DeathRule::Grave, DeathRule::LoseAll];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeathRule::Keep => "keep inventory",
+            DeathRule::Grave => "drop a grave",
+            DeathRule::LoseAll => "lose everything",
+        }
+    }
+
+    /// Name of the rule in save files.
+    pub fn key(&self) -> &'static str {
+        match self {
+            DeathRule::Keep => "keep",
+            DeathRule::Grave => "grave",
+            DeathRule::LoseAll => "lose_all",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        DeathRule::ALL.into_iter().find(|rule| rule.key() == key)
+    }
+
+    /// The rule after this one, wrapping around.
+    pub fn next(&self) -> Self {
+        DeathRule::ALL[(*self as usize + 1) % DeathRule::ALL.len()]
+    }
+}
+
+/// Picked in the settings before creating a world and kept in its save.
+#[derive(Clone, Copy, Default)]
+pub struct Rules {
+    pub on_death: DeathRule,
+    /// The world is deleted when the player dies.
+    pub hardcore: bool,
+}
@@ -0,0 +1,439 @@
+use std::{
+    cmp::Reverse,
+    fs,
+    io::{self, Error, ErrorKind},
+    path::{Path, PathBuf},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use crate::{
+    game::Game,
+    chunk::{Chunk, CHUNK_SIZE},
+    inventory::Inventory,
+    layer::Depth,
+    blocks::{BlockKind, Block, tree::Tree, grasstuft::GrassTuft, goldore::GoldOre, ironore::IronOre, coalore::CoalOre, stones::Stones, rock::Rock, sticks::Sticks, hole::Hole, ladder::Ladder, chest::Chest, bush::Bush, campfire::Campfire, grave::Grave},
+    entities::{self, EntityKind, Direction, player::Player},
+    items::ItemKind,
+    equipment::Slot,
+    rules::{Rules, DeathRule},
+    experience::Experience,
+};
+
+/// Bump this whenever the layout of a save file changes.
+pub const SAVE_VERSION: u32 = 9;
+const SAVE_DIR: &str = "saves";
+const SAVE_EXTENSION: &str = "sav";
+
+/// Path of the save file belonging to a world.
+pub fn save_path(seed: u32) -> PathBuf {
+    Path::new(SAVE_DIR).join(format!("world-{seed}.{SAVE_EXTENSION}"))
+}
+
+/// Remove the save file of a world, there may be none yet.
+pub fn delete(seed: u32) -> io::Result<()> {
+    match fs::remove_file(save_path(seed)) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// List every save file, most recently played first.
+pub fn list_saves() -> Vec<PathBuf> {
+    let mut saves = Vec::new();
+    if let Ok(entries) = fs::read_dir(SAVE_DIR) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == SAVE_EXTENSION) {
+                let modified = entry.metadata().and_then(|m| m.modified()).ok();
+                saves.push((modified, path));
+            }
+        }
+    }
+    saves.sort_by_key(|(modified, _)| Reverse(*modified));
+    saves.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Write the world and the player to the save file of the world.
+pub fn save(game: &Game, player: &mut Player) -> io::Result<()> {
+    fs::create_dir_all(SAVE_DIR)?;
+    save_at(game, player, &save_path(game.seed()))
+}
+
+/// Write the world and the player to the given file.
+pub fn save_at(game: &Game, player: &mut Player, path: &Path) -> io::Result<()> {
+    let mut lines = vec![
+        format!("yunikod-save {SAVE_VERSION}"),
+        format!("seed {}", game.seed()),
+        format!(
+            "player {} {} {} {} {}",
+            player.x(), player.y(), player.life(), player.max_life(), direction_key(&player.looking())
+        ),
+        format!("using {}", player.using()),
+        format!("hunger {}", player.hunger()),
+        format!("stats {} {}", player.age(), player.kills()),
+        format!("level {} {}", player.experience().level(), player.experience().xp()),
+        format!("rules {} {}", game.rules().on_death.key(), game.rules().hardcore),
+        format!("depth {}", game.depth().name()),
+    ];
+
+    let inventory = player.inventory();
+    for i in 0..inventory.len() {
+        let item = &inventory[i];
+        lines.push(format!("item {}", item_fields(item)));
+    }
+    for slot in Slot::ALL {
+        if let Some(item) = player.equipment().get(slot) {
+            lines.push(format!("equip {} {}", slot.key(), item_fields(item)));
+        }
+    }
+
+    for ((depth, x, y), items) in game.graves() {
+        for item in items {
+            lines.push(format!("grave {} {x} {y} {}", depth.name(), item_fields(item)));
+        }
+    }
+
+    let depth = game.depth().name();
+    for entity in game.entities() {
+        lines.extend(entity_line(depth, entity));
+    }
+    for (other, layer) in game.parked_layers() {
+        for entity in &layer.entities {
+            lines.extend(entity_line(other.name(), entity));
+        }
+    }
+
+    // only blocks that differ from the generated world are kept
+    for chunk in game.loaded_chunks().values() {
+        if !chunk.is_modified() {
+            continue;
+        }
+        let baseline = Chunk::generate(game.depth(), chunk.0, chunk.1, game.noise(), game.seed());
+        for (i, j) in chunk.changed_tiles(&baseline) {
+            lines.push(block_line(depth, (chunk.0, chunk.1), i, j, chunk[(i, j)].1.as_ref()));
+        }
+    }
+    let current = (game.depth(), game.chunk_deltas());
+    let parked = game.parked_layers().iter().map(|(depth, layer)| (*depth, &layer.chunk_deltas));
+    for (depth, deltas) in std::iter::once(current).chain(parked) {
+        for (chunk_idx, delta) in deltas {
+            for (i, j, block) in &delta.0 {
+                lines.push(block_line(depth.name(), *chunk_idx, *i, *j, block.as_ref()));
+            }
+        }
+    }
+
+    let current = (game.depth(), game.populated());
+    let parked = game.parked_layers().iter().map(|(depth, layer)| (*depth, &layer.populated));
+    for (depth, populated) in std::iter::once(current).chain(parked) {
+        for (col, row) in populated {
+            lines.push(format!("populated {} {col} {row}", depth.name()));
+        }
+    }
+
+    fs::write(path, lines.join("\n") + "\n")
+}
+
+/// Rebuild a world and its player from a save file.
+pub fn load(path: &Path) -> io::Result<(Game, Player)> {
+    let content = fs::read_to_string(path)?;
+    let mut lines = content.lines();
+
+    let header: Vec<&str> = lines.next().unwrap_or_default().split_whitespace().collect();
+    match header.as_slice() {
+        // the first version had no caves, everything in it is on the surface
+        ["yunikod-save", version] if matches!(version.parse(), Ok(1..=SAVE_VERSION)) => {},
+        ["yunikod-save", version] => return Err(invalid(format!("unsupported save version {version}"))),
+        _ => return Err(invalid("not a save file".to_string())),
+    }
+
+    let mut game: Option<Game> = None;
+    let mut player = Player::new(0, 0);
+    let mut inventory = Inventory::new();
+    let mut using = 0;
+    let mut equipped = Vec::new();
+    let mut rules = Rules::default();
+    let mut depth = Depth::Surface;
+
+    for line in lines {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            [] => {},
+            ["seed", seed] => game = Some(Game::new(parse(seed)?)),
+            ["player", x, y, life, max_life, looking] => {
+                player = Player::new(parse(x)?, parse(y)?);
+                player.set_life(parse(life)?, parse(max_life)?);
+                player.look(direction_from_key(looking)?);
+            },
+            ["using", idx] => using = parse(idx)?,
+            ["hunger", hunger] => player.set_hunger(parse(hunger)?),
+            ["stats", age, kills] => player.set_stats(parse(age)?, parse(kills)?),
+            ["level", level, xp] => player.set_experience(Experience::new(parse(level)?, parse(xp)?)),
+            ["rules", on_death, hardcore] => {
+                rules.on_death = DeathRule::from_key(on_death).ok_or_else(|| invalid(format!("unknown death rule '{on_death}'")))?;
+                rules.hardcore = parse(hardcore)?;
+            },
+            ["item", key, quantity, durability @ ..] if durability.len() <= 1 => {
+                inventory.add(item_from_fields(key, quantity, durability.first().copied())?);
+            },
+            ["equip", slot, key, quantity, durability @ ..] if durability.len() <= 1 => {
+                let slot = Slot::from_key(slot).ok_or_else(|| invalid(format!("unknown slot '{slot}'")))?;
+                equipped.push((slot, item_from_fields(key, quantity, durability.first().copied())?));
+            },
+            ["depth", name] => depth = depth_from_key(name)?,
+            ["entity", key, x, y, life] => {
+                let game = game.as_mut().ok_or_else(|| invalid("entity before seed".to_string()))?;
+                game.add_entity_at(Depth::Surface, entity_from_line(key, x, y, life)?);
+            },
+            ["entity", layer, key, x, y, life] => {
+                let game = game.as_mut().ok_or_else(|| invalid("entity before seed".to_string()))?;
+                game.add_entity_at(depth_from_key(layer)?, entity_from_line(key, x, y, life)?);
+            },
+            ["block", x, y, key, life] => {
+                let game = game.as_mut().ok_or_else(|| invalid("block before seed".to_string()))?;
+                game.set_block_at(Depth::Surface, parse(x)?, parse(y)?, block_from_line(key, life, None)?);
+            },
+            ["block", layer, x, y, key, life, contents @ ..] if contents.len() <= 1 => {
+                let game = game.as_mut().ok_or_else(|| invalid("block before seed".to_string()))?;
+                let block = block_from_line(key, life, contents.first().copied())?;
+                game.set_block_at(depth_from_key(layer)?, parse(x)?, parse(y)?, block);
+            },
+            ["grave", layer, x, y, key, quantity, durability @ ..] if durability.len() <= 1 => {
+                let game = game.as_mut().ok_or_else(|| invalid("grave before seed".to_string()))?;
+                let item = item_from_fields(key, quantity, durability.first().copied())?;
+                game.add_to_grave(depth_from_key(layer)?, parse(x)?, parse(y)?, item);
+            },
+            ["populated", layer, col, row] => {
+                let game = game.as_mut().ok_or_else(|| invalid("populated before seed".to_string()))?;
+                game.set_populated_at(depth_from_key(layer)?, (parse(col)?, parse(row)?));
+            },
+            _ => return Err(invalid(format!("unexpected line '{line}'"))),
+        }
+    }
+
+    let mut game = game.ok_or_else(|| invalid("missing seed".to_string()))?;
+    game.change_depth(depth);
+    game.set_rules(rules);
+    if inventory.is_empty() {
+        inventory = Inventory::new_player();
+    }
+    if using >= inventory.len() {
+        using = 0;
+    }
+    *player.inventory() = inventory;
+    player.set_using(using);
+    for (slot, item) in equipped {
+        player.equipment_mut().put(slot, item);
+    }
+    Ok((game, player))
+}
+
+/// Only creatures are saved, short lived entities such as fire or arrows are dropped.
+fn entity_line(depth: &str, entity: &EntityKind) -> Option<String> {
+    let key = entity_key(entity)?;
+    let (x, y) = entity.position();
+    Some(format!("entity {depth} {key} {x} {y} {}", entity.life()))
+}
+
+fn entity_from_line(key: &str, x: &str, y: &str, life: &str) -> io::Result<EntityKind> {
+    let mut entity = entity_from_key(key, parse(x)?, parse(y)?)?;
+    entity.set_life(parse(life)?);
+    Ok(entity)
+}
+
+fn block_line(depth: &str, chunk_idx: (i64, i64), i: usize, j: usize, block: Option<&BlockKind>) -> String {
+    let x = chunk_idx.0 * CHUNK_SIZE + i as i64;
+    let y = chunk_idx.1 * CHUNK_SIZE + j as i64;
+    match block {
+        Some(block) => match block.contents() {
+            Some(contents) => format!("block {depth} {x} {y} {} {} {contents}", block_key(block), block.life()),
+            None => format!("block {depth} {x} {y} {} {}", block_key(block), block.life()),
+        },
+        None => format!("block {depth} {x} {y} none 0"),
+    }
+}
+
+fn block_from_line(key: &str, life: &str, contents: Option<&str>) -> io::Result<Option<BlockKind>> {
+    if key == "none" {
+        return Ok(None);
+    }
+    let mut block = block_from_key(key, parse(life)?)?;
+    if let Some(contents) = contents {
+        block.set_contents(parse(contents)?);
+    }
+    Ok(Some(block))
+}
+
+fn depth_from_key(key: &str) -> io::Result<Depth> {
+    Depth::from_name(key).ok_or_else(|| invalid(format!("unknown depth '{key}'")))
+}
+
+fn invalid(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+fn parse<T: std::str::FromStr>(field: &str) -> io::Result<T> {
+    field.parse().map_err(|_| invalid(format!("invalid value '{field}'")))
+}
+
+fn direction_key(direction: &Direction) -> &'static str {
+    match direction {
+        Direction::Up => "up",
+        Direction::Down => "down",
+        Direction::Left => "left",
+        Direction::Right => "right",
+    }
+}
+
+fn direction_from_key(key: &str) -> io::Result<Direction> {
+    match key {
+        "up" => Ok(Direction::Up),
+        "down" => Ok(Direction::Down),
+        "left" => Ok(Direction::Left),
+        "right" => Ok(Direction::Right),
+        _ => Err(invalid(format!("unknown direction '{key}'"))),
+    }
+}
+
+fn entity_key(entity: &EntityKind) -> Option<&'static str> {
+    Some(entity.name()).filter(|name| entities::is_creature(name))
+}
+
+/// Key and quantity of an item, followed by the durability left for tools.
+fn item_fields(item: &ItemKind) -> String {
+    match item.as_tool() {
+        Some(tool) => format!("{} {} {}", item.key(), item.quantity(), tool.durability()),
+        None => format!("{} {}", item.key(), item.quantity()),
+    }
+}
+
+fn item_from_fields(key: &str, quantity: &str, durability: Option<&str>) -> io::Result<ItemKind> {
+    let mut item = ItemKind::from_key(key, parse(quantity)?).ok_or_else(|| invalid(format!("unknown item '{key}'")))?;
+    if let (Some(tool), Some(durability)) = (item.as_tool_mut(), durability) {
+        tool.set_durability(parse(durability)?);
+    }
+    Ok(item)
+}
+
+fn entity_from_key(key: &str, x: i64, y: i64) -> io::Result<EntityKind> {
+    entities::spawn(key, x, y).ok_or_else(|| invalid(format!("unknown entity '{key}'")))
+}
+
+fn block_key(block: &BlockKind) -> &'static str {
+    match block {
+        BlockKind::Tree(_) => "tree",
+        BlockKind::Grass(_) => "grass",
+        BlockKind::GoldOre(_) => "gold_ore",
+        BlockKind::IronOre(_) => "iron_ore",
+        BlockKind::CoalOre(_) => "coal_ore",
+        BlockKind::Stones(_) => "stones",
+        BlockKind::Rock(_) => "rock",
+        BlockKind::Sticks(_) => "sticks",
+        BlockKind::Hole(_) => "hole",
+        BlockKind::Ladder(_) => "ladder",
+        BlockKind::Chest(_) => "chest",
+        BlockKind::Bush(_) => "bush",
+        BlockKind::Campfire(_) => "campfire",
+        BlockKind::Grave(_) => "grave",
+    }
+}
+
+fn block_from_key(key: &str, life: u32) -> io::Result<BlockKind> {
+    // the life and contents are overwritten afterwards so the generator does not matter
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut block = match key {
+        "tree" => Tree::generate(&mut rng),
+        "grass" => GrassTuft::generate(&mut rng),
+        "gold_ore" => GoldOre::generate(&mut rng),
+        "iron_ore" => IronOre::generate(&mut rng),
+        "coal_ore" => CoalOre::generate(&mut rng),
+        "stones" => Stones::generate(&mut rng),
+        "rock" => Rock::generate(&mut rng),
+        "sticks" => Sticks::generate(&mut rng),
+        "hole" => Hole::generate(&mut rng),
+        "ladder" => Ladder::generate(&mut rng),
+        "chest" => Chest::generate(&mut rng),
+        "bush" => Bush::generate(&mut rng),
+        "campfire" => Campfire::generate(&mut rng),
+        "grave" => Grave::generate(&mut rng),
+        _ => return Err(invalid(format!("unknown block '{key}'"))),
+    };
+    block.set_life(life);
+    Ok(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::{pickaxe::Pickaxe, sword::Sword, helmet::Helmet, stone::Stone, tier::Tier};
+
+    /// A file of its own in the temp dir, removed when dropped.
+    struct TempSave(PathBuf);
+
+    impl TempSave {
+        fn new(name: &str) -> Self {
+            Self(std::env::temp_dir().join(format!("yunikod-{name}-{}.{SAVE_EXTENSION}", std::process::id())))
+        }
+    }
+
+    impl Drop for TempSave {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn item_lines(player: &mut Player) -> Vec<String> {
+        let inventory = player.inventory();
+        (0..inventory.len()).map(|i| item_fields(&inventory[i])).collect()
+    }
+
+    #[test]
+    fn a_saved_world_loads_back_the_same() {
+        let file = TempSave::new("round-trip");
+        let mut game = Game::new(42);
+        game.update_chunks();
+        game.set_block(3, 4, Some(Rock::generate(&mut StdRng::seed_from_u64(0))));
+        let mut chest = Chest::generate(&mut StdRng::seed_from_u64(0));
+        chest.set_contents(3);
+        game.set_block(8, 9, Some(chest));
+        game.bury(6, 5, vec![ItemKind::Stone(Stone::new(7))]);
+
+        let mut player = Player::new(1, 2);
+        player.set_life(60, 110);
+        player.set_hunger(40);
+        let mut pickaxe = ItemKind::Pickaxe(Pickaxe::new(Tier::Iron));
+        pickaxe.wear();
+        player.inventory().add(pickaxe);
+        player.equipment_mut().put(Slot::Head, ItemKind::Helmet(Helmet::new(1)));
+        player.equipment_mut().put(Slot::MainHand, ItemKind::Sword(Sword::new(Tier::Gold)));
+        save_at(&game, &mut player, &file.0).unwrap();
+
+        let (mut loaded, mut back) = load(&file.0).unwrap();
+        loaded.update_chunks();
+        assert_eq!(loaded.seed(), 42);
+        assert_eq!((back.x(), back.y(), back.life(), back.max_life(), back.hunger()), (1, 2, 60, 110, 40));
+        assert_eq!(item_lines(&mut back), item_lines(&mut player));
+        assert_eq!(item_lines(&mut back).last().unwrap(), "iron_pickaxe 1 149");
+        for slot in Slot::ALL {
+            assert_eq!(back.equipment().get(slot).map(item_fields), player.equipment().get(slot).map(item_fields));
+        }
+        assert!(matches!(loaded.get_block(3, 4), Some(BlockKind::Rock(_))));
+        assert_eq!(loaded.get_block(8, 9).and_then(|b| b.contents()), Some(3));
+        let &(_, x, y) = game.graves().keys().next().unwrap();
+        assert!(matches!(loaded.get_block(x, y), Some(BlockKind::Grave(_))));
+        assert_eq!(loaded.exhume(x, y).iter().map(item_fields).collect::<Vec<_>>(), ["stone 7"]);
+    }
+
+    #[test]
+    fn a_first_version_save_still_loads() {
+        let file = TempSave::new("version-1");
+        fs::write(&file.0, "yunikod-save 1\nseed 7\nplayer 3 4 80 100 left\nusing 1\nitem hand 1\nitem pickaxe 1\nentity snake 5 6 10\nblock 2 2 rock 1\n").unwrap();
+
+        let (mut game, mut player) = load(&file.0).unwrap();
+        game.update_chunks();
+        assert_eq!((player.x(), player.y(), player.life()), (3, 4, 80));
+        assert_eq!(player.using(), 1);
+        assert_eq!(item_lines(&mut player), ["hand 1", "stone_pickaxe 1 60"]);
+        assert!(game.entities().iter().any(|e| e.name() == "snake" && e.position() == (5, 6)));
+        assert!(matches!(game.get_block(2, 2), Some(BlockKind::Rock(_))));
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use crate::entities::{EntityId, EntityKind};
+
+/// Side of the square buckets entities are sorted in, in tiles.
+const BUCKET_SIZE: i64 = 8;
+
+/// An entity and the tile it stands on.
+type Entry = (EntityId, i64, i64);
+
+/// Entities sorted by where they stand so that looking around a tile
+/// only goes through the entities close to it.
+#[derive(Default)]
+pub struct SpatialIndex {
+    buckets: HashMap<(i64, i64), Vec<Entry>>,
+}
+
+impl SpatialIndex {
+    pub fn new(entities: &[EntityKind]) -> Self {
+        let mut index = Self::default();
+        for entity in entities {
+            index.insert(entity.id(), entity.x(), entity.y());
+        }
+        index
+    }
+
+    fn bucket(x: i64, y: i64) -> (i64, i64) {
+        (x.div_euclid(BUCKET_SIZE), y.div_euclid(BUCKET_SIZE))
+    }
+
+    pub fn insert(&mut self, id: EntityId, x: i64, y: i64) {
+        self.buckets.entry(Self::bucket(x, y)).or_default().push((id, x, y));
+    }
+
+    pub fn remove(&mut self, id: EntityId, x: i64, y: i64) {
+        let key = Self::bucket(x, y);
+        if let Some(bucket) = self.buckets.get_mut(&key) {
+            bucket.retain(|entry| entry.0 != id);
+            if bucket.is_empty() {
+                self.buckets.remove(&key);
+            }
+        }
+    }
+
+    /// Follow an entity which went from one tile to another.
+    pub fn relocate(&mut self, id: EntityId, from: (i64, i64), to: (i64, i64)) {
+        self.remove(id, from.0, from.1);
+        self.insert(id, to.0, to.1);
+    }
+
+    /// An entity standing on the tile.
+    pub fn at(&self, x: i64, y: i64) -> Option<EntityId> {
+        self.buckets.get(&Self::bucket(x, y))?
+            .iter()
+            .find(|entry| (entry.1, entry.2) == (x, y))
+            .map(|entry| entry.0)
+    }
+
+    /// Entities at most `radius` tiles away from (x, y) on both axes, with their position.
+    pub fn within(&self, x: i64, y: i64, radius: i64) -> impl Iterator<Item = Entry> + '_ {
+        let (i0, j0) = Self::bucket(x - radius, y - radius);
+        let (i1, j1) = Self::bucket(x + radius, y + radius);
+        (i0..=i1)
+            .flat_map(move |i| (j0..=j1).map(move |j| (i, j)))
+            .filter_map(|key| self.buckets.get(&key))
+            .flatten()
+            .copied()
+            .filter(move |entry| (entry.1 - x).abs() <= radius && (entry.2 - y).abs() <= radius)
+    }
+}
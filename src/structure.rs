@@ -0,0 +1,197 @@
+use std::sync::OnceLock;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use crate::{
+    biome::WorldNoise,
+    blocks::{Block, rock::Rock, stones::Stones, sticks::Sticks, chest::Chest, campfire::Campfire},
+    chunk::{Chunk, CHUNK_SIZE, mix},
+    entities::{EntityKind, snake::SNAKE, crawler::CRAWLER, golem::GOLEM},
+    layer::Depth,
+};
+
+/// The world is split in square regions of this many chunks, each holding at most one structure.
+const REGION_CHUNKS: i64 = 4;
+const REGION_SIZE: i64 = REGION_CHUNKS * CHUNK_SIZE;
+/// Keeps structure placement independent from the other generators using the seed.
+const STRUCTURE_SALT: u32 = 0x5bd1e995;
+const BOSS_LIFE: u8 = 30;
+
+const PREFABS: &str = include_str!("../assets/structures.txt");
+/// Starts the comment lines of the prefab file, '#' being a rock wall.
+const COMMENT: &str = "//";
+
+/// A structure as described in the prefab file.
+pub struct Prefab {
+    name: String,
+    depth: Depth,
+    chance: u32,
+    width: usize,
+    rows: Vec<Vec<char>>,
+}
+
+impl Prefab {
+    /// Every prefab of the data file, parsed on first use.
+    pub fn all() -> &'static [Prefab] {
+        static ALL: OnceLock<Vec<Prefab>> = OnceLock::new();
+        ALL.get_or_init(|| Prefab::parse(PREFABS))
+    }
+
+    fn parse(data: &str) -> Vec<Prefab> {
+        let mut prefabs: Vec<Prefab> = Vec::new();
+        for line in data.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with(COMMENT) {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let fields: Vec<&str> = header.split_whitespace().collect();
+                let [name, depth, chance] = fields.as_slice() else {
+                    panic!("invalid prefab header '{line}'");
+                };
+                prefabs.push(Prefab {
+                    name: name.to_string(),
+                    depth: Depth::from_name(depth).unwrap_or_else(|| panic!("unknown level '{depth}'")),
+                    chance: chance.parse().unwrap_or_else(|_| panic!("invalid chance '{chance}'")),
+                    width: 0,
+                    rows: Vec::new(),
+                });
+            } else if let Some(prefab) = prefabs.last_mut() {
+                prefab.width = prefab.width.max(line.chars().count());
+                prefab.rows.push(line.chars().collect());
+            }
+        }
+        prefabs
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Cell at the given column, counted from the left, and row, counted from the top.
+    fn cell(&self, column: usize, row: usize) -> char {
+        self.rows[row].get(column).copied().unwrap_or('_')
+    }
+}
+
+/// A prefab placed in the world, its bottom left corner is at (x, y).
+pub struct Structure {
+    prefab: &'static Prefab,
+    x: i64,
+    y: i64,
+    seed: u32,
+}
+
+impl Structure {
+    /// The structure of the region the chunk belongs to, if there is one.
+    /// It only depends on the seed so every chunk of the region agrees on it.
+    pub fn of_chunk(depth: Depth, col: i64, row: i64, noise: &WorldNoise, seed: u32) -> Option<Self> {
+        let region = (col.div_euclid(REGION_CHUNKS), row.div_euclid(REGION_CHUNKS));
+        let mut rng = StdRng::seed_from_u64(mix(seed ^ STRUCTURE_SALT, region.0, region.1));
+        let candidates: Vec<&Prefab> = Prefab::all().iter().filter(|p| p.depth == depth).collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        let prefab = candidates[rng.gen_range(0..candidates.len())];
+        if !rng.gen_ratio(prefab.chance, 100) {
+            return None;
+        }
+        let x = region.0 * REGION_SIZE + rng.gen_range(0..=REGION_SIZE - prefab.width as i64);
+        let y = region.1 * REGION_SIZE + rng.gen_range(0..=REGION_SIZE - prefab.height() as i64);
+        let structure = Self { prefab, x, y, seed };
+        structure.fits(depth, noise).then_some(structure)
+    }
+
+    /// Surface structures are not built on water nor on mountains.
+    fn fits(&self, depth: Depth, noise: &WorldNoise) -> bool {
+        depth != Depth::Surface || self.cells().all(|(x, y, _)| {
+            let height = noise.height.get_noise(x as f64, y as f64);
+            (0.0..30.0).contains(&height)
+        })
+    }
+
+    /// World coordinates and content of every cell the structure changes.
+    fn cells(&self) -> impl Iterator<Item = (i64, i64, char)> + '_ {
+        let (width, height) = (self.prefab.width, self.prefab.height());
+        (0..height).flat_map(move |row| (0..width).map(move |column| (column, row)))
+            .map(|(column, row)| {
+                let x = self.x + column as i64;
+                let y = self.y + (self.prefab.height() - 1 - row) as i64;
+                (x, y, self.prefab.cell(column, row))
+            })
+            .filter(|(_, _, cell)| *cell != '_')
+    }
+
+    fn cells_in_chunk(&self, col: i64, row: i64) -> impl Iterator<Item = (i64, i64, char)> + '_ {
+        let x_range = col * CHUNK_SIZE..(col + 1) * CHUNK_SIZE;
+        let y_range = row * CHUNK_SIZE..(row + 1) * CHUNK_SIZE;
+        self.cells().filter(move |(x, y, _)| x_range.contains(x) && y_range.contains(y))
+    }
+
+    /// Build the part of the structure lying in the chunk.
+    pub fn stamp(&self, chunk: &mut Chunk) {
+        for (x, y, cell) in self.cells_in_chunk(chunk.0, chunk.1) {
+            // seeded by the tile so that it does not matter which chunk is built first
+            let mut rng = StdRng::seed_from_u64(mix(self.seed, x, y));
+            let tile = ((x - chunk.0 * CHUNK_SIZE) as usize, (y - chunk.1 * CHUNK_SIZE) as usize);
+            // rivers flowing through the structure are left alone
+            if chunk[tile].0.is_water() {
+                continue;
+            }
+            chunk[tile].1 = match cell {
+                '#' => Some(Rock::generate(&mut rng)),
+                'o' => Some(Stones::generate(&mut rng)),
+                's' => Some(Sticks::generate(&mut rng)),
+                'c' => Some(Chest::generate(&mut rng)),
+                'f' => Some(Campfire::generate(&mut rng)),
+                _ => None,
+            };
+        }
+    }
+
+    /// Creatures living in the part of the structure lying in the chunk.
+    pub fn inhabitants(&self, col: i64, row: i64) -> Vec<EntityKind> {
+        self.cells_in_chunk(col, row)
+            .filter_map(|(x, y, cell)| match cell {
+                'n' => Some(SNAKE.spawn(x, y)),
+                'k' => Some(CRAWLER.spawn(x, y)),
+                'g' => Some(GOLEM.spawn(x, y)),
+                'B' => {
+                    let mut boss = GOLEM.spawn(x, y);
+                    boss.set_life(BOSS_LIFE);
+                    Some(boss)
+                },
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comments_between_prefabs_are_not_rows() {
+        let prefabs = Prefab::parse("// walls\n[hut surface 10]\n// a comment\n#.#\n\n// another\n[camp surface 5]\n.f.\n");
+        assert_eq!(prefabs.len(), 2);
+        assert_eq!(prefabs[0].rows, [vec!['#', '.', '#']]);
+        assert_eq!(prefabs[1].rows, [vec!['.', 'f', '.']]);
+    }
+
+    #[test]
+    fn shipped_prefabs_parse_and_fit_in_a_region() {
+        let prefabs = Prefab::all();
+        assert!(!prefabs.is_empty());
+        for prefab in prefabs {
+            assert!(prefab.height() > 0, "{} has no rows", prefab.name);
+            assert!(prefab.width as i64 <= REGION_SIZE && prefab.height() as i64 <= REGION_SIZE, "{} is larger than a region", prefab.name);
+            assert!(prefab.chance <= 100, "{} has a chance above 100", prefab.name);
+            for cell in prefab.rows.iter().flatten() {
+                assert!("_.#oscfnkgB".contains(*cell), "{} has an unknown cell '{cell}'", prefab.name);
+            }
+        }
+    }
+}
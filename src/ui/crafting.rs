@@ -13,26 +13,28 @@ use crate::{
     game::Game, inventory::Recipe
 };
 
-pub fn run<B: Backend>(terminal: &mut Terminal<B>, game: &mut Game, mut player: &mut Player) -> io::Result<u8> {
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, game: &mut Game, player: &mut Player) -> io::Result<u8> {
     let mut list_idx = 0;
     loop {
         // draw \\
-        terminal.draw(|frame| draw(frame, &game, &mut player, list_idx))?;
+        terminal.draw(|frame| draw(frame, game, player, list_idx))?;
 
         // input handler \\
         if let Event::Key(key) = event::read()? {
             match key.code {
                 KeyCode::Esc => return Ok(0),
                 KeyCode::Enter => {
-                    let msg = player.inventory().craft(&Recipe::recipes()[list_idx]);
+                    let recipe = &Recipe::recipes()[list_idx];
+                    let crafted = player.inventory().can_craft(recipe);
+                    let mut msg = player.inventory().craft(recipe);
+                    let level = if crafted { player.gain_xp(recipe.xp()) } else { None };
+                    if let Some(level) = level {
+                        msg = format!("{}, {}", msg, level);
+                    }
                     game.set_message(msg);
                 },
-                KeyCode::Up => {
-                    if list_idx > 0 {list_idx -= 1};
-                },
-                KeyCode::Down => {
-                    if list_idx < Recipe::recipes().len() - 1 {list_idx += 1};
-                },
+                KeyCode::Up => list_idx = list_idx.saturating_sub(1),
+                KeyCode::Down if list_idx < Recipe::recipes().len() - 1 => list_idx += 1,
                 KeyCode::Left => return Ok(1),
                 KeyCode::Right => return Ok(3),
                 _ => {}
@@ -42,7 +44,7 @@ pub fn run<B: Backend>(terminal: &mut Terminal<B>, game: &mut Game, mut player:
     }
 }
 
-fn draw<'a, B: Backend>(frame: &mut Frame<B>, game: &Game, player: &mut Player, list_idx: usize) {
+fn draw<B: Backend>(frame: &mut Frame<B>, game: &Game, player: &mut Player, list_idx: usize) {
     let vchunks = Layout::default()
         .constraints([Constraint::Length(3), Constraint::Min(3), Constraint::Length(6), Constraint::Length(3)])
         .split(frame.size());
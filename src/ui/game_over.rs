@@ -0,0 +1,59 @@
+use crossterm::event::{self, Event, KeyCode};
+use tui::{
+    Frame,
+    Terminal,
+    backend::Backend,
+    style::{Style, Color, Modifier},
+    layout::{Layout, Constraint, Alignment},
+    widgets::{Block, Borders, Paragraph}, text::{Spans, Span}
+};
+use std::io;
+use crate::{entities::player::Player, effects::TICKS_PER_SECOND};
+
+/// Tell how the player died, returns whether they come back to life.
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, player: &Player, hardcore: bool) -> io::Result<bool> {
+    loop {
+        // draw \\
+        terminal.draw(|frame| draw(frame, player, hardcore))?;
+
+        // input handler \\
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Char('r') if !hardcore => return Ok(true),
+                KeyCode::Esc | KeyCode::Enter => return Ok(false),
+                _ => {}
+            }
+        }
+
+    }
+}
+
+fn draw<B: Backend>(frame: &mut Frame<B>, player: &Player, hardcore: bool) {
+    let vchunks = Layout::default()
+        .constraints([Constraint::Min(9), Constraint::Length(3)])
+        .split(frame.size());
+
+    let seconds = player.age() / TICKS_PER_SECOND as u32;
+    let cause = if player.cause().is_empty() { "died" } else { player.cause() };
+    let mut lines = vec![
+        Spans::from(Span::styled("GAME OVER", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))),
+        Spans::from(""),
+        Spans::from(format!("you {}", cause)),
+        Spans::from(format!("survived {}:{:02}", seconds / 60, seconds % 60)),
+        Spans::from(format!("kills: {}", player.kills())),
+    ];
+    if hardcore {
+        lines.push(Spans::from(""));
+        lines.push(Spans::from(Span::styled("this hardcore world is lost for good", Style::default().fg(Color::DarkGray))));
+    }
+    let para_summary = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL))
+        .alignment(Alignment::Center);
+    frame.render_widget(para_summary, vchunks[0]);
+
+    let actions = if hardcore { "[Esc] main menu" } else { "[r] respawn | [Esc] main menu" };
+    let para_action = Paragraph::new(actions)
+        .block(Block::default().borders(Borders::ALL))
+        .alignment(Alignment::Center);
+    frame.render_widget(para_action, vchunks[1]);
+}
@@ -10,27 +10,44 @@ use tui::{
 use std::io;
 use crate::{
     entities::player::Player,
+    equipment::Slot,
     game::Game
 };
 
-pub fn run<B: Backend>(terminal: &mut Terminal<B>, game: &mut Game, mut player: &mut Player) -> io::Result<u8> {
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, game: &mut Game, player: &mut Player) -> io::Result<u8> {
     let mut list_idx = 0;
     loop {
         // draw \\
-        terminal.draw(|frame| draw(frame, &game, &mut player, list_idx))?;
+        terminal.draw(|frame| draw(frame, game, player, list_idx))?;
 
         // input handler \\
         if let Event::Key(key) = event::read()? {
             match key.code {
                 KeyCode::Esc => return Ok(0),
-                KeyCode::Up => {
-                    if list_idx > 0 {list_idx -= 1};
+                KeyCode::Up => list_idx = list_idx.saturating_sub(1),
+                KeyCode::Down if list_idx < player.inventory().len() - 1 => list_idx += 1,
+                KeyCode::Right => return Ok(2),
+                KeyCode::Enter => {
+                    // food is eaten right away, anything else is taken in hand
+                    match player.eat(list_idx) {
+                        Some(message) => game.set_message(message),
+                        None => player.set_using(list_idx),
+                    }
+                    list_idx = list_idx.min(player.inventory().len() - 1);
                 },
-                KeyCode::Down => {
-                    if list_idx < player.inventory().len() - 1 {list_idx += 1};
+                KeyCode::Char('d') => {
+                    if let Some(item) = player.drop_item(list_idx) {
+                        game.drop_item(player.x(), player.y(), item);
+                        list_idx = list_idx.min(player.inventory().len() - 1);
+                    }
+                },
+                KeyCode::Char('e') => {
+                    player.equip(list_idx);
+                    list_idx = list_idx.min(player.inventory().len() - 1);
+                },
+                KeyCode::Char(c @ '1'..='4') => {
+                    player.unequip(Slot::ALL[c as usize - '1' as usize]);
                 },
-                KeyCode::Right => return Ok(2),
-                KeyCode::Enter => player.set_using(list_idx),
                 _ => {}
             }
         }
@@ -38,9 +55,9 @@ pub fn run<B: Backend>(terminal: &mut Terminal<B>, game: &mut Game, mut player:
     }
 }
 
-fn draw<'a, B: Backend>(frame: &mut Frame<B>, _game: &Game, player: &mut Player, list_idx: usize) {
+fn draw<B: Backend>(frame: &mut Frame<B>, _game: &Game, player: &mut Player, list_idx: usize) {
     let vchunks = Layout::default()
-        .constraints([Constraint::Length(3), Constraint::Length(3), Constraint::Min(3), Constraint::Length(3)])
+        .constraints([Constraint::Length(3), Constraint::Length(6), Constraint::Min(3), Constraint::Length(3)])
         .split(frame.size());
 
     let hchunks0 = Layout::default()
@@ -61,19 +78,45 @@ fn draw<'a, B: Backend>(frame: &mut Frame<B>, _game: &Game, player: &mut Player,
         .block(Block::default().title("[Tab]").borders(Borders::ALL));
     frame.render_widget(para_tabs, hchunks0[0]);
 
+    let hchunks2 = Layout::default()
+        .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)])
+        .direction(tui::layout::Direction::Horizontal)
+        .split(hchunks0[1]);
+
     let gauge_lifebar = Gauge::default()
         .block(Block::default().title("[Life]").borders(Borders::ALL))
         .gauge_style(Style::default().fg(Color::Red))
         .ratio(player.life_ratio());
-    frame.render_widget(gauge_lifebar, hchunks0[1]);
+    frame.render_widget(gauge_lifebar, hchunks2[0]);
+
+    let gauge_hungerbar = Gauge::default()
+        .block(Block::default().title("[Hunger]").borders(Borders::ALL))
+        .gauge_style(Style::default().fg(Color::Rgb(145, 77, 5)))
+        .ratio(player.hunger_ratio());
+    frame.render_widget(gauge_hungerbar, hchunks2[1]);
+
+    // unlocked perks are listed next to the level
+    let experience = player.experience();
+    let mut title = format!("[Level {}]", experience.level());
+    for perk in experience.perks() {
+        title = format!("{} {}", title, perk.name());
+    }
+    let gauge_xpbar = Gauge::default()
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .gauge_style(Style::default().fg(Color::LightGreen))
+        .label(experience.label())
+        .ratio(experience.ratio());
+    frame.render_widget(gauge_xpbar, hchunks2[2]);
 
     let idx = player.using();
     let para_using = Paragraph::new(format!("[k] using: {}", player.inventory().get(idx).name()))
         .block(Block::default().borders(Borders::ALL));
     frame.render_widget(para_using, hchunks1[0]);
 
-    let para_equiped = Paragraph::new(format!("[m] equiped:"))
-        .block(Block::default().borders(Borders::ALL));
+    let equipment = player.equipment();
+    let title = format!("[1-4] take off | defense: {}", equipment.defense());
+    let para_equiped = Paragraph::new(equipment.to_spans())
+        .block(Block::default().title(title).borders(Borders::ALL));
     frame.render_widget(para_equiped, hchunks1[1]);
 
     let mut list_state = ListState::default();
@@ -83,7 +126,7 @@ fn draw<'a, B: Backend>(frame: &mut Frame<B>, _game: &Game, player: &mut Player,
         .highlight_symbol(">");
     frame.render_stateful_widget(list, vchunks[2], &mut list_state);
 
-    let para_action = Paragraph::new("action")
+    let para_action = Paragraph::new("[Enter] use or eat | [d] drop | [e] equip")
         .block(Block::default().borders(Borders::ALL));
     frame.render_widget(para_action, vchunks[3]);
 }
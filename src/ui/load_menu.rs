@@ -0,0 +1,66 @@
+use crossterm::event::{self, Event, KeyCode};
+use tui::{
+    Frame,
+    Terminal,
+    backend::Backend,
+    style::{Style, Color},
+    layout::{Layout, Constraint},
+    widgets::{Block, Borders, Paragraph, List, ListItem, ListState}
+};
+use std::{io, path::PathBuf};
+use crate::save;
+use super::main_menu;
+
+pub fn run<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
+    let saves = save::list_saves();
+    let mut list_idx = 0;
+    let mut message = String::from("[Enter] load | [Esc] back");
+    loop {
+        // draw \\
+        terminal.draw(|frame| draw(frame, &saves, list_idx, &message))?;
+
+        // input handler \\
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Esc => return Ok(()),
+                KeyCode::Up => list_idx = list_idx.saturating_sub(1),
+                KeyCode::Down if list_idx + 1 < saves.len() => list_idx += 1,
+                KeyCode::Enter => {
+                    if let Some(path) = saves.get(list_idx) {
+                        match main_menu::load_game(terminal, path) {
+                            Ok(()) => return Ok(()),
+                            Err(error) => message = format!("could not load: {error}"),
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+
+    }
+}
+
+fn draw<B: Backend>(frame: &mut Frame<B>, saves: &[PathBuf], list_idx: usize, message: &str) {
+    let vchunks = Layout::default()
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(frame.size());
+
+    let items: Vec<ListItem> = saves
+        .iter()
+        .map(|path| {
+            let name = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+            ListItem::new(name)
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    list_state.select(Some(list_idx));
+    let list = List::new(items)
+        .block(Block::default().title("load game").borders(Borders::ALL))
+        .highlight_style(Style::default().fg(Color::Magenta))
+        .highlight_symbol(">");
+    frame.render_stateful_widget(list, vchunks[0], &mut list_state);
+
+    let para_message = Paragraph::new(message.to_string())
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(para_message, vchunks[1]);
+}
@@ -7,9 +7,11 @@ use tui::{
     layout::{Layout, Constraint, Alignment},
     widgets::{Block, Borders, Paragraph}, text::{Spans, Span, Text}
 };
-use std::io;
+use rand::random;
+use std::{io, path::Path};
 
-use crate::{game::{self, Game}, entities::player::Player};
+use crate::{game::{self, Game}, entities::player::Player, rules::Rules, save};
+use super::{load_menu, settings};
 
 fn build_title<'a>(color: Color) -> Text<'a> {
     let style = Style::default().fg(color);
@@ -34,33 +36,44 @@ fn build_title<'a>(color: Color) -> Text<'a> {
     ])
 }
 
+const ENTRIES: [(&str, Color); 5] = [
+    ("NEW GAME", Color::Blue),
+    ("CONTINUE", Color::Cyan),
+    ("LOAD GAME", Color::Magenta),
+    ("SETTINGS", Color::Green),
+    ("EXIT", Color::Red),
+];
+
 pub fn run<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<u8> {
     let mut list_idx = 0;
+    let mut rules = Rules::default();
+    let mut message = String::new();
     loop {
-        let color = match list_idx {
-            0 => Color::Blue,
-            1 => Color::Green,
-            2 => Color::Red,
-            _ => Color::Blue
-        };
+        let color = ENTRIES[list_idx].1;
+        let has_saves = !save::list_saves().is_empty();
         // draw \\
-        terminal.draw(|frame| draw(frame, list_idx, color))?;
+        terminal.draw(|frame| draw(frame, list_idx, color, has_saves, &message))?;
 
         // input handler \\
         if let Event::Key(key) = event::read()? {
             match key.code {
                 KeyCode::Esc => return Ok(0),
-                KeyCode::Up => {
-                    if list_idx > 0 {list_idx -= 1};
-                },
-                KeyCode::Down => {
-                    if list_idx < 2 {list_idx += 1};
-                },
+                KeyCode::Up => list_idx = list_idx.saturating_sub(1),
+                KeyCode::Down if list_idx < ENTRIES.len() - 1 => list_idx += 1,
                 KeyCode::Enter => {
+                    message.clear();
                     match list_idx {
-                        0 => new_game(terminal)?,
-                        1 => {},
-                        2 => return Ok(0),
+                        0 => new_game(terminal, rules)?,
+                        1 => {
+                            if let Some(path) = save::list_saves().first() {
+                                if let Err(error) = load_game(terminal, path) {
+                                    message = format!("could not load: {error}");
+                                }
+                            }
+                        },
+                        2 if has_saves => load_menu::run(terminal)?,
+                        3 => settings::run(terminal, &mut rules)?,
+                        4 => return Ok(0),
                         _ => {}
                     }
                 },
@@ -71,53 +84,56 @@ pub fn run<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<u8> {
     }
 }
 
-fn draw<'a, B: Backend>(frame: &mut Frame<B>, list_idx: usize, color: Color) {
+fn draw<B: Backend>(frame: &mut Frame<B>, list_idx: usize, color: Color, has_saves: bool, message: &str) {
+    let mut constraints = vec![Constraint::Length(7)];
+    constraints.extend([Constraint::Length(3); ENTRIES.len()]);
+    constraints.push(Constraint::Min(0));
     let mut vchunks = Layout::default()
-        .constraints([Constraint::Length(7), Constraint::Length(3), Constraint::Length(3), Constraint::Length(3), Constraint::Min(0)])
+        .constraints(constraints)
         .split(frame.size());
-    
-    let mut blocks = Vec::new();
-    for i in 1..=3 {
-        vchunks[i].width = 40;
-        vchunks[i].x = frame.size().width/2-20;
-        if list_idx == i-1 {
-            blocks.push(Block::default().borders(Borders::ALL).style(Style::default().fg(color)))
-        } else {
-            blocks.push(Block::default().borders(Borders::ALL))
-        }
-    }
 
     let para_title = Paragraph::new(build_title(color))
         .block(Block::default().borders(Borders::ALL))
         .alignment(Alignment::Center);
     frame.render_widget(para_title, vchunks[0]);
-    
-    let para_new_game = Paragraph::new(Span::styled("NEW GAME", Style::default().fg(Color::Blue)))
-        .block(blocks[0].clone())
-        .alignment(Alignment::Center);
-    frame.render_widget(para_new_game, vchunks[1]);
-    
-    let para_settings = Paragraph::new(Span::styled("SETTINGS", Style::default().fg(Color::Green)))
-        .block(blocks[1].clone())
-        .alignment(Alignment::Center);
-    frame.render_widget(para_settings, vchunks[2]);
 
-    let para_exit = Paragraph::new(Span::styled("EXIT", Style::default().fg(Color::Red)))
-        .block(blocks[2].clone())
+    for (i, (label, entry_color)) in ENTRIES.iter().enumerate() {
+        let area = &mut vchunks[i+1];
+        area.width = 40;
+        area.x = frame.size().width/2-20;
+        let block = if list_idx == i {
+            Block::default().borders(Borders::ALL).style(Style::default().fg(color))
+        } else {
+            Block::default().borders(Borders::ALL)
+        };
+        // continuing or loading needs an existing save
+        let entry_color = if (i == 1 || i == 2) && !has_saves {
+            Color::DarkGray
+        } else { *entry_color };
+        let para_entry = Paragraph::new(Span::styled(*label, Style::default().fg(entry_color)))
+            .block(block)
+            .alignment(Alignment::Center);
+        frame.render_widget(para_entry, *area);
+    }
+
+    let para_message = Paragraph::new(Span::styled(message.to_string(), Style::default().fg(Color::Red)))
         .alignment(Alignment::Center);
-    frame.render_widget(para_exit, vchunks[3]);
+    frame.render_widget(para_message, vchunks[ENTRIES.len()+1]);
 }
 
-fn new_game<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<()>{
-    let mut game = Game::new();
+fn new_game<B: Backend>(terminal: &mut Terminal<B>, rules: Rules) -> io::Result<()>{
+    let mut game = Game::new(random());
+    game.set_rules(rules);
     game.update_chunks();
-    let mut x = 0.0;
-    while game.perlin().get_noise(x, 0.0) < 0.0 {
-        x += 1.0
-    }
-    //let player = Player::new(x as i64, 0);
-    let mut player = Player::new(x as i64, 0);
+    let (x, y) = game.spawn_point();
+    let mut player = Player::new(x, y);
     player.inventory().add(crate::items::ItemKind::Stick(crate::items::stick::Stick::new(20)));
     game::run(terminal, game, player)?;
     Ok(())
 }
+
+pub fn load_game<B: Backend>(terminal: &mut Terminal<B>, path: &Path) -> io::Result<()> {
+    let (mut game, player) = save::load(path)?;
+    game.update_chunks();
+    game::run(terminal, game, player)
+}
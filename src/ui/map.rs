@@ -13,10 +13,10 @@ use crate::{
     game::Game
 };
 
-pub fn run<B: Backend>(terminal: &mut Terminal<B>, game: &mut Game, mut player: &mut Player) -> io::Result<u8> {
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, game: &mut Game, player: &mut Player) -> io::Result<u8> {
     loop {
         // draw \\
-        terminal.draw(|frame| draw(frame, &game, &mut player))?;
+        terminal.draw(|frame| draw(frame, game, player))?;
 
         // input handler \\
         if let Event::Key(key) = event::read()? {
@@ -31,7 +31,7 @@ pub fn run<B: Backend>(terminal: &mut Terminal<B>, game: &mut Game, mut player:
     }
 }
 
-fn draw<'a, B: Backend>(frame: &mut Frame<B>, game: &Game, player: &mut Player) {
+fn draw<B: Backend>(frame: &mut Frame<B>, game: &Game, player: &mut Player) {
     let vchunks = Layout::default()
         .constraints([Constraint::Length(3), Constraint::Min(3), Constraint::Length(3)])
         .split(frame.size());
@@ -63,11 +63,8 @@ fn draw<'a, B: Backend>(frame: &mut Frame<B>, game: &Game, player: &mut Player)
         .y_bounds([-h, h])
         .block(Block::default().title("map").borders(Borders::ALL))
         .paint(|ctx| {
-            for chunk in game.loaded_chunks() {
-                ctx.print(chunk.0 as f64, chunk.1 as f64, chunk.average_terrain().span());
-            }
-            for chunk in game.unused_chunks() {
-                ctx.print(chunk.0 as f64, chunk.1 as f64, chunk.average_terrain().span());
+            for ((i, j), terrain) in game.explored() {
+                ctx.print(*i as f64, *j as f64, terrain.span());
             }
             ctx.print((player.x()/16) as f64, (player.y()/16) as f64, Span::styled("+", Style::default().fg(Color::Red)));
         });
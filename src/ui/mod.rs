@@ -2,3 +2,6 @@ pub mod inventory;
 pub mod crafting;
 pub mod map;
 pub mod main_menu;
+pub mod load_menu;
+pub mod game_over;
+pub mod settings;
@@ -0,0 +1,59 @@
+use crossterm::event::{self, Event, KeyCode};
+use tui::{
+    Frame,
+    Terminal,
+    backend::Backend,
+    style::{Style, Color},
+    layout::{Layout, Constraint},
+    widgets::{Block, Borders, Paragraph, List, ListItem, ListState}
+};
+use std::io;
+use crate::rules::Rules;
+
+const ENTRIES: usize = 2;
+
+/// Pick the rules of the worlds created next.
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, rules: &mut Rules) -> io::Result<()> {
+    let mut list_idx = 0;
+    loop {
+        // draw \\
+        terminal.draw(|frame| draw(frame, rules, list_idx))?;
+
+        // input handler \\
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Esc => return Ok(()),
+                KeyCode::Up => list_idx = list_idx.saturating_sub(1),
+                KeyCode::Down if list_idx + 1 < ENTRIES => list_idx += 1,
+                KeyCode::Enter | KeyCode::Left | KeyCode::Right => match list_idx {
+                    0 => rules.on_death = rules.on_death.next(),
+                    _ => rules.hardcore = !rules.hardcore,
+                },
+                _ => {}
+            }
+        }
+
+    }
+}
+
+fn draw<B: Backend>(frame: &mut Frame<B>, rules: &Rules, list_idx: usize) {
+    let vchunks = Layout::default()
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(frame.size());
+
+    let items = vec![
+        ListItem::new(format!("on death: {}", rules.on_death.name())),
+        ListItem::new(format!("hardcore: {}", if rules.hardcore { "on, the world is deleted on death" } else { "off" })),
+    ];
+    let mut list_state = ListState::default();
+    list_state.select(Some(list_idx));
+    let list = List::new(items)
+        .block(Block::default().title("settings").borders(Borders::ALL))
+        .highlight_style(Style::default().fg(Color::Green))
+        .highlight_symbol(">");
+    frame.render_stateful_widget(list, vchunks[0], &mut list_state);
+
+    let para_message = Paragraph::new("[Enter] change | [Esc] back | applies to new worlds")
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(para_message, vchunks[1]);
+}